@@ -0,0 +1,642 @@
+//! Python bindings to the [`libpressio`] crate.
+//!
+//! The bindings expose [`Pressio`], [`PressioCompressor`],
+//! [`PressioSendableCompressor`], [`PressioOptions`], and [`PressioData`] as
+//! the Python classes `Pressio`, `Compressor`, `SendableCompressor`,
+//! `Options`, and `Data`. C-contiguous numpy arrays are passed to libpressio
+//! without copying.
+
+use std::{
+    cell::{RefCell, RefMut},
+    sync::{Mutex, MutexGuard},
+};
+
+use libpressio::{
+    Pressio, PressioArray, PressioCompressor, PressioConversionSafety, PressioData, PressioDtype,
+    PressioElement, PressioOption, PressioOptions, PressioSendableCompressor,
+};
+use numpy::{
+    Element, PyArray1, PyArrayDescr, PyArrayDescrMethods, PyArrayDyn, PyArrayMethods,
+    PyReadonlyArrayDyn, PyUntypedArrayMethods,
+};
+use pyo3::{
+    IntoPyObjectExt, create_exception,
+    exceptions::{PyException, PyKeyError, PyRuntimeError, PyTypeError, PyValueError},
+    prelude::*,
+    types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString},
+};
+
+create_exception!(
+    pressio,
+    PressioError,
+    PyException,
+    "An error reported by libpressio."
+);
+
+fn to_py_err(err: libpressio::PressioError) -> PyErr {
+    PressioError::new_err(err.message)
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> PyResult<MutexGuard<'_, T>> {
+    mutex
+        .lock()
+        .map_err(|_| PyRuntimeError::new_err("a previous libpressio call panicked"))
+}
+
+/// An instance of the libpressio library.
+#[pyclass(name = "Pressio", module = "pressio", frozen)]
+struct PyPressio {
+    inner: Mutex<Pressio>,
+}
+
+#[pymethods]
+impl PyPressio {
+    #[new]
+    fn new() -> PyResult<Self> {
+        Ok(Self {
+            inner: Mutex::new(Pressio::new().map_err(to_py_err)?),
+        })
+    }
+
+    /// Instantiate the compressor with the given `id`.
+    fn get_compressor(&self, id: &str) -> PyResult<PyCompressor> {
+        let compressor = lock(&self.inner)?.get_compressor(id).map_err(to_py_err)?;
+        Ok(PyCompressor {
+            inner: RefCell::new(Some(compressor)),
+        })
+    }
+}
+
+/// Access to the compressor wrapped by a Python class.
+trait WithCompressor {
+    fn with_compressor<O>(
+        &self,
+        py: Python<'_>,
+        with: impl FnOnce(&mut PressioCompressor) -> PyResult<O>,
+    ) -> PyResult<O>;
+}
+
+/// Generate the `#[pymethods]` that are shared by `Compressor` and
+/// `SendableCompressor`, together with the `extra` methods of the class.
+macro_rules! compressor_methods {
+    ($class:ty { $($extra:tt)* }) => {
+        #[pymethods]
+        impl $class {
+            $($extra)*
+
+            /// Compress the `input`, which is either a numpy array or `Data`.
+            ///
+            /// The GIL is released while libpressio compresses the data.
+            fn compress(&self, input: &Bound<'_, PyAny>) -> PyResult<PyData> {
+                let py = input.py();
+                self.with_compressor(py, |compressor| {
+                    with_input_data(input, |input| {
+                        let compressed = detach(py, compressor, input, |compressor, input| {
+                            compressor.compress(input, PressioData::new_empty(PressioDtype::Byte, []))
+                        })
+                        .map_err(to_py_err)?;
+                        Ok(PyData::from(compressed))
+                    })
+                })
+            }
+
+            /// Decompress the `compressed` data, which is either a numpy
+            /// array or `Data`, into a numpy array with the given `dtype`
+            /// and `shape`.
+            ///
+            /// The GIL is released while libpressio decompresses the data.
+            fn decompress<'py>(
+                &self,
+                compressed: &Bound<'py, PyAny>,
+                dtype: &Bound<'py, PyAny>,
+                shape: Vec<usize>,
+            ) -> PyResult<Bound<'py, PyAny>> {
+                let py = compressed.py();
+                let dtype = pressio_dtype(&PyArrayDescr::new(py, dtype)?)?;
+                let decompressed = self.with_compressor(py, |compressor| {
+                    with_input_data(compressed, |compressed| {
+                        detach(py, compressor, compressed, |compressor, compressed| {
+                            compressor.decompress(compressed, PressioData::new_empty(dtype, &shape))
+                        })
+                        .map_err(to_py_err)
+                    })
+                })?;
+                data_to_numpy(py, &decompressed)
+            }
+
+            fn get_options(&self, py: Python<'_>) -> PyResult<PyOptions> {
+                self.with_compressor(py, |compressor| {
+                    Ok(PyOptions::from(compressor.get_options().map_err(to_py_err)?))
+                })
+            }
+
+            /// Set the `options`, which are either `Options` or a `dict`.
+            ///
+            /// Values are converted to the types of the compressor's options.
+            fn set_options(&self, options: &Bound<'_, PyAny>) -> PyResult<()> {
+                self.with_compressor(options.py(), |compressor| {
+                    let options = if let Ok(options) = options.cast::<PyOptions>() {
+                        lock(&options.get().inner)?.clone()
+                    } else if let Ok(dict) = options.cast::<PyDict>() {
+                        let template = compressor.get_options().map_err(to_py_err)?;
+                        let mut options = PressioOptions::new().map_err(to_py_err)?;
+                        for (key, value) in dict.iter() {
+                            let key = key.extract::<String>()?;
+                            set_typed_option(&mut options, &template, &key, &value)?;
+                        }
+                        options
+                    } else {
+                        return Err(PyTypeError::new_err("expected Options or a dict"));
+                    };
+                    compressor.set_options(&options).map_err(to_py_err)
+                })
+            }
+
+            fn get_configuration(&self, py: Python<'_>) -> PyResult<PyOptions> {
+                self.with_compressor(py, |compressor| {
+                    Ok(PyOptions::from(compressor.get_configuration().map_err(to_py_err)?))
+                })
+            }
+
+            fn get_documentation(&self, py: Python<'_>) -> PyResult<PyOptions> {
+                self.with_compressor(py, |compressor| {
+                    Ok(PyOptions::from(compressor.get_documentation().map_err(to_py_err)?))
+                })
+            }
+
+            fn get_metric_results(&self, py: Python<'_>) -> PyResult<PyOptions> {
+                self.with_compressor(py, |compressor| {
+                    Ok(PyOptions::from(compressor.get_metric_results().map_err(to_py_err)?))
+                })
+            }
+
+            #[getter]
+            fn prefix(&self, py: Python<'_>) -> PyResult<String> {
+                self.with_compressor(py, |compressor| {
+                    Ok(String::from(compressor.get_prefix().map_err(to_py_err)?))
+                })
+            }
+
+            #[getter]
+            fn name(&self, py: Python<'_>) -> PyResult<String> {
+                self.with_compressor(py, |compressor| {
+                    Ok(String::from(compressor.get_name().map_err(to_py_err)?))
+                })
+            }
+        }
+    };
+}
+
+/// Wrapper that allows passing libpressio objects into [`Python::detach`].
+struct AssertUngil<T>(T);
+
+// SAFETY: the wrapped libpressio objects do not reference any Python objects,
+//         and Python::detach runs its closure on the current thread
+unsafe impl<T> Send for AssertUngil<T> {}
+
+impl<T> AssertUngil<T> {
+    // the closure in detach must capture the whole wrapper, not its fields
+    fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// Run the libpressio call `f` on the `compressor` and `data` without holding
+/// the GIL, such that other Python threads can run in the meantime.
+fn detach<T: Send>(
+    py: Python<'_>,
+    compressor: &mut PressioCompressor,
+    data: &PressioData,
+    f: impl Send + FnOnce(&mut PressioCompressor, &PressioData) -> T,
+) -> T {
+    let args = AssertUngil((compressor, data));
+    py.detach(move || {
+        let (compressor, data) = args.into_inner();
+        f(compressor, data)
+    })
+}
+
+/// A compressor that can only be used from the thread that created it.
+#[pyclass(name = "Compressor", module = "pressio", unsendable)]
+struct PyCompressor {
+    // None after the compressor has been converted into a sendable one
+    inner: RefCell<Option<PressioCompressor>>,
+}
+
+impl PyCompressor {
+    fn borrow_mut(&self) -> PyResult<RefMut<'_, Option<PressioCompressor>>> {
+        self.inner
+            .try_borrow_mut()
+            .map_err(|_| PyRuntimeError::new_err("the compressor is already in use"))
+    }
+}
+
+impl WithCompressor for PyCompressor {
+    fn with_compressor<O>(
+        &self,
+        _py: Python<'_>,
+        with: impl FnOnce(&mut PressioCompressor) -> PyResult<O>,
+    ) -> PyResult<O> {
+        match &mut *self.borrow_mut()? {
+            Some(compressor) => with(compressor),
+            None => Err(PyRuntimeError::new_err(
+                "the compressor has been converted into a SendableCompressor",
+            )),
+        }
+    }
+}
+
+compressor_methods!(PyCompressor {
+    /// Convert the compressor into one that can be shared across threads.
+    ///
+    /// This compressor can no longer be used afterwards. An error is raised
+    /// if the compressor is not thread-safe, in which case this compressor
+    /// remains usable.
+    fn try_into_sendable(&self) -> PyResult<PySendableCompressor> {
+        let mut inner = self.borrow_mut()?;
+        let Some(compressor) = inner.take() else {
+            return Err(PyRuntimeError::new_err(
+                "the compressor has already been converted into a SendableCompressor",
+            ));
+        };
+        match compressor.try_into_sendable() {
+            Ok(compressor) => Ok(PySendableCompressor {
+                inner: Mutex::new(compressor),
+            }),
+            Err((compressor, err)) => {
+                *inner = Some(compressor);
+                Err(to_py_err(err))
+            }
+        }
+    }
+});
+
+/// A thread-safe compressor that can be shared across threads.
+///
+/// Concurrent calls on the same compressor wait for each other.
+#[pyclass(name = "SendableCompressor", module = "pressio", frozen)]
+struct PySendableCompressor {
+    inner: Mutex<PressioSendableCompressor>,
+}
+
+impl WithCompressor for PySendableCompressor {
+    fn with_compressor<O>(
+        &self,
+        py: Python<'_>,
+        with: impl FnOnce(&mut PressioCompressor) -> PyResult<O>,
+    ) -> PyResult<O> {
+        // wait for the lock without holding the GIL, since its holder may
+        // need the GIL to finish its call
+        let inner = &self.inner;
+        let mut compressor = py.detach(move || AssertUngil(lock(inner))).into_inner()?;
+        with(&mut compressor)
+    }
+}
+
+compressor_methods!(PySendableCompressor {});
+
+/// A collection of typed libpressio options.
+#[pyclass(name = "Options", module = "pressio", frozen)]
+struct PyOptions {
+    inner: Mutex<PressioOptions>,
+}
+
+impl From<PressioOptions> for PyOptions {
+    fn from(options: PressioOptions) -> Self {
+        Self {
+            inner: Mutex::new(options),
+        }
+    }
+}
+
+#[pymethods]
+impl PyOptions {
+    #[new]
+    fn new() -> PyResult<Self> {
+        Ok(Self::from(PressioOptions::new().map_err(to_py_err)?))
+    }
+
+    fn __len__(&self) -> PyResult<usize> {
+        Ok(lock(&self.inner)?.len())
+    }
+
+    fn __contains__(&self, key: &str) -> PyResult<bool> {
+        lock(&self.inner)?.has_option(key).map_err(to_py_err)
+    }
+
+    fn __getitem__<'py>(&self, py: Python<'py>, key: &str) -> PyResult<Bound<'py, PyAny>> {
+        match lock(&self.inner)?.get(key).map_err(to_py_err)? {
+            Some(option) => option_to_py(py, option),
+            None => Err(PyKeyError::new_err(String::from(key))),
+        }
+    }
+
+    /// Set the option `key` to the `value`.
+    ///
+    /// If the option already has a type, the value is converted to it.
+    fn __setitem__(&self, key: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        let mut options = lock(&self.inner)?;
+        let template = options.clone();
+        set_typed_option(&mut options, &template, key, value)
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        Ok(PyList::new(py, self.keys()?)?.try_iter()?.into_any())
+    }
+
+    fn keys(&self) -> PyResult<Vec<String>> {
+        let options = lock(&self.inner)?;
+        Ok(options.iter().filter_map(|(key, _)| key).collect())
+    }
+
+    /// Convert the options into a `dict` of Python values.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        for entry in &*lock(&self.inner)? {
+            let (key, option) = entry.map_err(to_py_err)?;
+            dict.set_item(key, option_to_py(py, option)?)?;
+        }
+        Ok(dict)
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("Options({})", lock(&self.inner)?))
+    }
+}
+
+/// A libpressio data buffer.
+#[pyclass(name = "Data", module = "pressio", frozen)]
+struct PyData {
+    inner: Mutex<PressioData>,
+}
+
+impl From<PressioData> for PyData {
+    fn from(data: PressioData) -> Self {
+        Self {
+            inner: Mutex::new(data),
+        }
+    }
+}
+
+#[pymethods]
+impl PyData {
+    /// Copy a numpy array into new data.
+    #[staticmethod]
+    fn from_numpy(array: &Bound<'_, PyAny>) -> PyResult<Self> {
+        with_input_data(array, |data| Ok(Self::from(data.clone())))
+    }
+
+    /// Copy the data into a new numpy array.
+    fn to_numpy<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        data_to_numpy(py, &*lock(&self.inner)?)
+    }
+
+    #[getter]
+    fn dtype(&self) -> PyResult<Option<String>> {
+        Ok(lock(&self.inner)?.dtype().map(|dtype| dtype.to_string()))
+    }
+
+    #[getter]
+    fn shape(&self) -> PyResult<Vec<usize>> {
+        Ok(lock(&self.inner)?.shape())
+    }
+
+    fn __len__(&self) -> PyResult<usize> {
+        Ok(lock(&self.inner)?.len())
+    }
+
+    fn num_bytes(&self) -> PyResult<usize> {
+        Ok(lock(&self.inner)?.num_bytes())
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("{:?}", lock(&self.inner)?))
+    }
+}
+
+/// Call `with` on the `input`, which is either `Data` or a numpy array.
+///
+/// C-contiguous numpy arrays are shared with libpressio without copying.
+fn with_input_data<O>(
+    input: &Bound<'_, PyAny>,
+    with: impl FnOnce(&PressioData) -> PyResult<O>,
+) -> PyResult<O> {
+    if let Ok(data) = input.cast::<PyData>() {
+        return with(&*lock(&data.get().inner)?);
+    }
+
+    macro_rules! try_element {
+        ($($element:ty),*) => {
+            $(
+                if let Ok(array) = input.cast::<PyArrayDyn<$element>>() {
+                    return with_numpy_array(&array.try_readonly()?, with);
+                }
+            )*
+        };
+    }
+    try_element!(bool, u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+
+    Err(PyTypeError::new_err(
+        "expected Data or a numpy array with a dtype that libpressio supports",
+    ))
+}
+
+fn with_numpy_array<T: Element + PressioElement, O>(
+    array: &PyReadonlyArrayDyn<'_, T>,
+    with: impl FnOnce(&PressioData) -> PyResult<O>,
+) -> PyResult<O> {
+    let shape = array.shape().to_vec();
+
+    if array.is_c_contiguous() {
+        if let Ok(values) = array.as_slice() {
+            let values = ndarray::ArrayView::from_shape(shape, values)
+                .map_err(|err| PyValueError::new_err(err.to_string()))?;
+            return PressioData::new_with_shared(values, with);
+        }
+    }
+
+    // numpy iterates over the array in C order
+    let values = array.as_array().iter().copied().collect::<Vec<_>>();
+    let values = ndarray::Array::from_shape_vec(shape, values)
+        .map_err(|err| PyValueError::new_err(err.to_string()))?;
+    PressioData::new_with_shared(values, with)
+}
+
+fn data_to_numpy<'py>(py: Python<'py>, data: &PressioData) -> PyResult<Bound<'py, PyAny>> {
+    fn array_to_numpy<'py, T: Element>(
+        py: Python<'py>,
+        array: ndarray::ArrayD<T>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let shape = array.shape().to_vec();
+        let (values, _offset) = array.into_raw_vec_and_offset();
+        Ok(PyArray1::from_vec(py, values).reshape(shape)?.into_any())
+    }
+
+    match data.clone_into_array() {
+        Some(PressioArray::Bool(array)) => array_to_numpy(py, array),
+        Some(PressioArray::Byte(array) | PressioArray::U8(array)) => array_to_numpy(py, array),
+        Some(PressioArray::U16(array)) => array_to_numpy(py, array),
+        Some(PressioArray::U32(array)) => array_to_numpy(py, array),
+        Some(PressioArray::U64(array)) => array_to_numpy(py, array),
+        Some(PressioArray::I8(array)) => array_to_numpy(py, array),
+        Some(PressioArray::I16(array)) => array_to_numpy(py, array),
+        Some(PressioArray::I32(array)) => array_to_numpy(py, array),
+        Some(PressioArray::I64(array)) => array_to_numpy(py, array),
+        Some(PressioArray::F32(array)) => array_to_numpy(py, array),
+        Some(PressioArray::F64(array)) => array_to_numpy(py, array),
+        None => Err(PyValueError::new_err(
+            "cannot convert data without values to numpy",
+        )),
+    }
+}
+
+fn pressio_dtype(descr: &Bound<'_, PyArrayDescr>) -> PyResult<PressioDtype> {
+    let py = descr.py();
+
+    macro_rules! try_element {
+        ($($element:ty => $dtype:ident),*) => {
+            $(
+                if descr.is_equiv_to(&numpy::dtype::<$element>(py)) {
+                    return Ok(PressioDtype::$dtype);
+                }
+            )*
+        };
+    }
+    try_element!(
+        bool => Bool, u8 => U8, u16 => U16, u32 => U32, u64 => U64, i8 => I8,
+        i16 => I16, i32 => I32, i64 => I64, f32 => F32, f64 => F64
+    );
+
+    Err(PyTypeError::new_err(format!(
+        "libpressio does not support the dtype {descr}"
+    )))
+}
+
+fn option_to_py(py: Python<'_>, option: PressioOption) -> PyResult<Bound<'_, PyAny>> {
+    match option {
+        PressioOption::bool(Some(x)) => x.into_bound_py_any(py),
+        PressioOption::int8(Some(x)) => x.into_bound_py_any(py),
+        PressioOption::int16(Some(x)) => x.into_bound_py_any(py),
+        PressioOption::int32(Some(x)) => x.into_bound_py_any(py),
+        PressioOption::int64(Some(x)) => x.into_bound_py_any(py),
+        PressioOption::uint8(Some(x)) => x.into_bound_py_any(py),
+        PressioOption::uint16(Some(x)) => x.into_bound_py_any(py),
+        PressioOption::uint32(Some(x)) => x.into_bound_py_any(py),
+        PressioOption::uint64(Some(x)) => x.into_bound_py_any(py),
+        PressioOption::float32(Some(x)) => x.into_bound_py_any(py),
+        PressioOption::float64(Some(x)) => x.into_bound_py_any(py),
+        PressioOption::string(Some(x)) => x.into_bound_py_any(py),
+        PressioOption::vec_string(Some(x)) => x.into_bound_py_any(py),
+        PressioOption::data(Some(x)) => PyData::from(x).into_bound_py_any(py),
+        PressioOption::dtype(Some(x)) => x.to_string().into_bound_py_any(py),
+        PressioOption::thread_safety(Some(x)) => x.to_string().into_bound_py_any(py),
+        _ => Ok(py.None().into_bound(py)),
+    }
+}
+
+/// Convert a Python value into an option, inferring its type.
+fn option_from_py(value: &Bound<'_, PyAny>) -> PyResult<PressioOption> {
+    if value.is_none() {
+        Ok(PressioOption::unset)
+    } else if value.is_instance_of::<PyBool>() {
+        Ok(PressioOption::bool(Some(value.extract()?)))
+    } else if value.is_instance_of::<PyInt>() {
+        match value.extract::<i64>() {
+            Ok(x) => Ok(PressioOption::int64(Some(x))),
+            Err(_) => Ok(PressioOption::uint64(Some(value.extract()?))),
+        }
+    } else if value.is_instance_of::<PyFloat>() {
+        Ok(PressioOption::float64(Some(value.extract()?)))
+    } else if value.is_instance_of::<PyString>() {
+        Ok(PressioOption::string(Some(value.extract()?)))
+    } else if let Ok(data) = value.cast::<PyData>() {
+        Ok(PressioOption::data(Some(lock(&data.get().inner)?.clone())))
+    } else if let Ok(strings) = value.extract::<Vec<String>>() {
+        Ok(PressioOption::vec_string(Some(strings)))
+    } else {
+        Err(PyTypeError::new_err(format!(
+            "unsupported option value of type {}",
+            value.get_type()
+        )))
+    }
+}
+
+/// Set the option `key` to the `value`, converted to the type of the same
+/// option in the `template` if it has one.
+fn set_typed_option(
+    options: &mut PressioOptions,
+    template: &PressioOptions,
+    key: &str,
+    value: &Bound<'_, PyAny>,
+) -> PyResult<()> {
+    let value = option_from_py(value)?;
+
+    match template.get(key).map_err(to_py_err)? {
+        Some(option_type)
+            if !matches!(option_type, PressioOption::unset)
+                && option_type.type_name() != value.type_name() =>
+        {
+            options
+                .set(key, option_type.copy_type_only())
+                .map_err(to_py_err)?;
+            options
+                .set_with_cast(key, value, PressioConversionSafety::Explicit)
+                .map_err(|err| {
+                    PyTypeError::new_err(format!(
+                        "cannot convert the value to the {} type of option `{key}`: {}",
+                        option_type.type_name(),
+                        err.message
+                    ))
+                })
+        }
+        _ => options.set(key, value).map_err(to_py_err),
+    }
+}
+
+#[pyfunction]
+fn supported_compressors() -> PyResult<Vec<&'static str>> {
+    libpressio::supported_compressors().map_err(to_py_err)
+}
+
+#[pyfunction]
+fn supported_io_modules() -> PyResult<Vec<&'static str>> {
+    libpressio::supported_io_modules().map_err(to_py_err)
+}
+
+#[pyfunction]
+fn supported_metrics() -> PyResult<Vec<&'static str>> {
+    libpressio::supported_metrics().map_err(to_py_err)
+}
+
+#[pyfunction]
+fn features() -> PyResult<Vec<&'static str>> {
+    libpressio::features().map_err(to_py_err)
+}
+
+/// The `(major, minor, patch)` version of libpressio.
+#[pyfunction]
+fn version() -> (u32, u32, u32) {
+    (
+        libpressio::major_version(),
+        libpressio::minor_version(),
+        libpressio::patch_version(),
+    )
+}
+
+#[pymodule]
+fn pressio(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyPressio>()?;
+    m.add_class::<PyCompressor>()?;
+    m.add_class::<PySendableCompressor>()?;
+    m.add_class::<PyOptions>()?;
+    m.add_class::<PyData>()?;
+    m.add("PressioError", m.py().get_type::<PressioError>())?;
+
+    m.add_function(wrap_pyfunction!(supported_compressors, m)?)?;
+    m.add_function(wrap_pyfunction!(supported_io_modules, m)?)?;
+    m.add_function(wrap_pyfunction!(supported_metrics, m)?)?;
+    m.add_function(wrap_pyfunction!(features, m)?)?;
+    m.add_function(wrap_pyfunction!(version, m)?)?;
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+from concurrent.futures import ThreadPoolExecutor
+
+import numpy as np
+import pytest
+
+import pressio
+
+
+@pytest.fixture
+def lib():
+    return pressio.Pressio()
+
+
+def test_compress_decompress_roundtrip(lib):
+    compressor = lib.get_compressor("noop")
+    data = np.arange(30 * 30, dtype=np.float32).reshape(30, 30)
+
+    compressed = compressor.compress(data)
+    assert isinstance(compressed, pressio.Data)
+
+    decompressed = compressor.decompress(compressed, data.dtype, data.shape)
+    assert decompressed.dtype == data.dtype
+    np.testing.assert_array_equal(decompressed, data)
+
+
+def test_compress_non_contiguous_roundtrip(lib):
+    compressor = lib.get_compressor("noop")
+    data = np.arange(6 * 8, dtype=np.int32).reshape(6, 8)[:, ::2]
+
+    compressed = compressor.compress(data)
+    decompressed = compressor.decompress(compressed, data.dtype, data.shape)
+    np.testing.assert_array_equal(decompressed, data)
+
+
+def test_try_into_sendable(lib):
+    compressor = lib.get_compressor("noop")
+    sendable = compressor.try_into_sendable()
+    assert isinstance(sendable, pressio.SendableCompressor)
+
+    with pytest.raises(RuntimeError, match="converted into a SendableCompressor"):
+        compressor.try_into_sendable()
+    with pytest.raises(RuntimeError, match="converted into a SendableCompressor"):
+        compressor.compress(np.zeros(4, dtype=np.float32))
+
+    def roundtrip(offset):
+        data = np.arange(30 * 30, dtype=np.float32).reshape(30, 30) + offset
+        for _ in range(50):
+            compressed = sendable.compress(data)
+            decompressed = sendable.decompress(compressed, data.dtype, data.shape)
+            np.testing.assert_array_equal(decompressed, data)
+
+    # both threads share the compressor, and their failures are re-raised
+    with ThreadPoolExecutor(max_workers=2) as pool:
+        list(pool.map(roundtrip, [0, 1000]))
+
+
+def test_data_roundtrip():
+    data = np.array([[1.5, -2.0], [0.25, 4.0]])
+
+    wrapped = pressio.Data.from_numpy(data)
+    assert wrapped.shape == [2, 2]
+    assert len(wrapped) == 4
+    np.testing.assert_array_equal(wrapped.to_numpy(), data)
+
+
+def test_compressor_options(lib):
+    compressor = lib.get_compressor("pressio")
+
+    compressor.set_options({"pressio:metric": "size", "pressio:abs": 1})
+    options = compressor.get_options()
+    assert "pressio:metric" in options
+    assert options["pressio:metric"] == "size"
+    # the integer is converted to the float type of the option
+    assert isinstance(options["pressio:abs"], float)
+    assert options["pressio:abs"] == 1.0
+    assert options.to_dict()["pressio:abs"] == 1.0
+
+    with pytest.raises(TypeError):
+        compressor.set_options({"pressio:abs": "not a number"})
+
+
+def test_options_get_set():
+    options = pressio.Options()
+    assert len(options) == 0
+
+    options["a:int"] = 3
+    options["a:str"] = "value"
+    options["a:list"] = ["x", "y"]
+    assert options["a:int"] == 3
+    assert options["a:str"] == "value"
+    assert options["a:list"] == ["x", "y"]
+    assert sorted(options.keys()) == ["a:int", "a:list", "a:str"]
+
+    with pytest.raises(KeyError):
+        options["missing"]
+
+
+def test_errors(lib):
+    with pytest.raises(pressio.PressioError):
+        lib.get_compressor("does-not-exist")
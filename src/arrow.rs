@@ -0,0 +1,282 @@
+use std::{ops::Deref, ptr::NonNull, sync::Arc};
+
+use arrow_array::{
+    Array, ArrayRef, ArrowPrimitiveType, BooleanArray, FixedSizeListArray, PrimitiveArray,
+    types::{
+        Float32Type, Float64Type, Int8Type, Int16Type, Int32Type, Int64Type, UInt8Type, UInt16Type,
+        UInt32Type, UInt64Type,
+    },
+};
+use arrow_buffer::{ArrowNativeType, Buffer, ScalarBuffer};
+use arrow_schema::{DataType, Field};
+
+use crate::{PressioData, PressioDtype, PressioError};
+
+impl PressioDtype {
+    /// The Arrow data type with the same element representation.
+    ///
+    /// [`PressioDtype::Byte`] is mapped to [`DataType::UInt8`].
+    pub const fn to_arrow_type(self) -> DataType {
+        match self {
+            Self::Bool => DataType::Boolean,
+            Self::Byte | Self::U8 => DataType::UInt8,
+            Self::U16 => DataType::UInt16,
+            Self::U32 => DataType::UInt32,
+            Self::U64 => DataType::UInt64,
+            Self::I8 => DataType::Int8,
+            Self::I16 => DataType::Int16,
+            Self::I32 => DataType::Int32,
+            Self::I64 => DataType::Int64,
+            Self::F32 => DataType::Float32,
+            Self::F64 => DataType::Float64,
+        }
+    }
+
+    /// The dtype for a primitive Arrow data type, or [`None`] if libpressio
+    /// has no equivalent dtype.
+    pub const fn from_arrow_type(data_type: &DataType) -> Option<Self> {
+        match data_type {
+            DataType::Boolean => Some(Self::Bool),
+            DataType::UInt8 => Some(Self::U8),
+            DataType::UInt16 => Some(Self::U16),
+            DataType::UInt32 => Some(Self::U32),
+            DataType::UInt64 => Some(Self::U64),
+            DataType::Int8 => Some(Self::I8),
+            DataType::Int16 => Some(Self::I16),
+            DataType::Int32 => Some(Self::I32),
+            DataType::Int64 => Some(Self::I64),
+            DataType::Float32 => Some(Self::F32),
+            DataType::Float64 => Some(Self::F64),
+            _ => None,
+        }
+    }
+}
+
+/// [`PressioData`] that is converted from an Arrow array and may share its
+/// buffer.
+///
+/// The data dereferences to a [`PressioData`], which can e.g. be compressed
+/// directly.
+pub struct ArrowPressioData {
+    // the data must be dropped before the buffer that it may point into
+    data: PressioData,
+    _buffer: Option<Buffer>,
+}
+
+impl Deref for ArrowPressioData {
+    type Target = PressioData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl PressioData {
+    /// Convert a primitive Arrow array into one-dimensional data.
+    ///
+    /// The values buffer of the array is shared without copying, except for
+    /// boolean arrays, which Arrow packs into bits. An error is returned if
+    /// the array contains nulls or if its data type has no [`PressioDtype`].
+    pub fn from_arrow(array: &dyn Array) -> Result<ArrowPressioData, PressioError> {
+        Self::from_arrow_with_shape(array, &[array.len()])
+    }
+
+    /// Convert an Arrow fixed-size-list array of primitive values into a
+    /// tensor.
+    ///
+    /// Each list is one row of the tensor, i.e. the data has the shape
+    /// `[array.len(), inner_shape...]`. If the `inner_shape` is empty, each
+    /// list is treated as one-dimensional. The `inner_shape` must otherwise
+    /// have as many elements as each list. See [`PressioData::from_arrow`]
+    /// for when the values are shared without copying.
+    pub fn from_arrow_tensor(
+        array: &FixedSizeListArray,
+        inner_shape: &[usize],
+    ) -> Result<ArrowPressioData, PressioError> {
+        let list_len = usize::try_from(array.value_length()).unwrap_or(0);
+
+        let mut shape = vec![array.len()];
+        if inner_shape.is_empty() {
+            shape.push(list_len);
+        } else if inner_shape.iter().product::<usize>() == list_len {
+            shape.extend_from_slice(inner_shape);
+        } else {
+            return Err(arrow_error(&format!(
+                "inner shape {inner_shape:?} does not match the list length {list_len}"
+            )));
+        }
+
+        if array.null_count() > 0 {
+            return Err(arrow_error("cannot convert Arrow arrays with nulls"));
+        }
+
+        Self::from_arrow_with_shape(array.values().as_ref(), &shape)
+    }
+
+    /// Convert the data into a one-dimensional primitive Arrow array.
+    ///
+    /// The data is moved into the Arrow array without copying if it is
+    /// sufficiently aligned, except for [`PressioDtype::Bool`] data, which
+    /// Arrow packs into bits. An error is returned if the data has no values.
+    pub fn into_arrow(self) -> Result<ArrayRef, PressioError> {
+        let (Some(dtype), true) = (self.dtype(), self.has_data()) else {
+            return Err(arrow_error("cannot convert data without values to Arrow"));
+        };
+
+        Ok(match dtype {
+            PressioDtype::Bool => {
+                let values = self.as_bytes().unwrap_or_default();
+                Arc::new(BooleanArray::from_iter(
+                    values.iter().map(|&value| Some(value != 0)),
+                ))
+            }
+            PressioDtype::Byte | PressioDtype::U8 => into_primitive_array::<UInt8Type>(self),
+            PressioDtype::U16 => into_primitive_array::<UInt16Type>(self),
+            PressioDtype::U32 => into_primitive_array::<UInt32Type>(self),
+            PressioDtype::U64 => into_primitive_array::<UInt64Type>(self),
+            PressioDtype::I8 => into_primitive_array::<Int8Type>(self),
+            PressioDtype::I16 => into_primitive_array::<Int16Type>(self),
+            PressioDtype::I32 => into_primitive_array::<Int32Type>(self),
+            PressioDtype::I64 => into_primitive_array::<Int64Type>(self),
+            PressioDtype::F32 => into_primitive_array::<Float32Type>(self),
+            PressioDtype::F64 => into_primitive_array::<Float64Type>(self),
+        })
+    }
+
+    /// Convert the data into an Arrow fixed-size-list array, with one list
+    /// per index along the first dimension.
+    ///
+    /// See [`PressioData::into_arrow`] for when the data is not copied. An
+    /// error is returned if the data has fewer than two dimensions or if its
+    /// trailing dimensions are empty.
+    pub fn into_arrow_tensor(self) -> Result<FixedSizeListArray, PressioError> {
+        let shape = self.shape();
+        let list_len = match shape.as_slice() {
+            [_, inner @ ..] if !inner.is_empty() => inner.iter().product::<usize>(),
+            _ => {
+                return Err(arrow_error(
+                    "cannot convert data with fewer than two dimensions to an Arrow tensor",
+                ));
+            }
+        };
+        let list_len = match i32::try_from(list_len) {
+            Ok(list_len) if list_len > 0 => list_len,
+            _ => {
+                return Err(arrow_error(&format!(
+                    "cannot convert data with shape {shape:?} to an Arrow tensor"
+                )));
+            }
+        };
+
+        let values = self.into_arrow()?;
+        let field = Arc::new(Field::new_list_field(values.data_type().clone(), false));
+        FixedSizeListArray::try_new(field, list_len, values, None)
+            .map_err(|err| arrow_error(&err.to_string()))
+    }
+
+    fn from_arrow_with_shape(
+        array: &dyn Array,
+        shape: &[usize],
+    ) -> Result<ArrowPressioData, PressioError> {
+        let Some(dtype) = PressioDtype::from_arrow_type(array.data_type()) else {
+            return Err(arrow_error(&format!(
+                "unsupported Arrow data type {}",
+                array.data_type()
+            )));
+        };
+        if array.null_count() > 0 {
+            return Err(arrow_error("cannot convert Arrow arrays with nulls"));
+        }
+
+        let buffer = match dtype {
+            PressioDtype::Bool => {
+                let Some(array) = array.as_any().downcast_ref::<BooleanArray>() else {
+                    unreachable!("Arrow arrays of booleans must be BooleanArrays");
+                };
+                let values = array.values().iter().map(u8::from).collect::<Vec<_>>();
+                return Ok(ArrowPressioData {
+                    data: Self::from_bytes(dtype, shape, &values),
+                    _buffer: None,
+                });
+            }
+            PressioDtype::Byte | PressioDtype::U8 => primitive_buffer::<UInt8Type>(array),
+            PressioDtype::U16 => primitive_buffer::<UInt16Type>(array),
+            PressioDtype::U32 => primitive_buffer::<UInt32Type>(array),
+            PressioDtype::U64 => primitive_buffer::<UInt64Type>(array),
+            PressioDtype::I8 => primitive_buffer::<Int8Type>(array),
+            PressioDtype::I16 => primitive_buffer::<Int16Type>(array),
+            PressioDtype::I32 => primitive_buffer::<Int32Type>(array),
+            PressioDtype::I64 => primitive_buffer::<Int64Type>(array),
+            PressioDtype::F32 => primitive_buffer::<Float32Type>(array),
+            PressioDtype::F64 => primitive_buffer::<Float64Type>(array),
+        };
+
+        let data = unsafe {
+            libpressio_sys::pressio_data_new_nonowning(
+                dtype.into_raw(),
+                // SAFETY: we only give access to &PressioData, which does
+                //         not expose mutating access, so we can cast a
+                //         const ptr to a mut ptr here
+                buffer.as_ptr().cast_mut().cast(),
+                shape.len(),
+                shape.as_ptr(),
+            )
+        };
+        let data = NonNull::new(data).expect("pressio_data_new_nonowning must not return null");
+
+        Ok(ArrowPressioData {
+            data: Self { data },
+            _buffer: Some(buffer),
+        })
+    }
+}
+
+fn primitive_buffer<T: ArrowPrimitiveType>(array: &dyn Array) -> Buffer {
+    let Some(array) = array.as_any().downcast_ref::<PrimitiveArray<T>>() else {
+        unreachable!("Arrow arrays of primitives must be PrimitiveArrays");
+    };
+    array.values().inner().clone()
+}
+
+fn into_primitive_array<T: ArrowPrimitiveType>(data: PressioData) -> ArrayRef {
+    Arc::new(PrimitiveArray::<T>::new(into_scalar_buffer(data), None))
+}
+
+fn into_scalar_buffer<T: ArrowNativeType>(data: PressioData) -> ScalarBuffer<T> {
+    /// Keeps the data alive while Arrow shares its buffer.
+    struct PressioDataAllocation(#[expect(dead_code)] PressioData);
+
+    // Safety: Arrow never accesses the allocation, it only drops it
+    unsafe impl Sync for PressioDataAllocation {}
+
+    let len = data.len();
+    let bytes = data.as_bytes().unwrap_or_default();
+
+    let ptr = NonNull::new(bytes.as_ptr().cast_mut());
+    let buffer = match ptr {
+        Some(ptr) if ptr.cast::<T>().is_aligned() => {
+            let num_bytes = bytes.len();
+            // Safety: the pointer is valid for num_bytes bytes while the data
+            //         is alive, and moving the data does not move its buffer
+            unsafe {
+                Buffer::from_custom_allocation(
+                    ptr,
+                    num_bytes,
+                    Arc::new(PressioDataAllocation(data)),
+                )
+            }
+        }
+        // copy the data into a new buffer, ensuring that our copy is
+        // properly aligned, no matter the alignment in libpressio
+        _ => Buffer::from_slice_ref(bytes),
+    };
+
+    ScalarBuffer::new(buffer, 0, len)
+}
+
+fn arrow_error(message: &str) -> PressioError {
+    PressioError {
+        error_code: 1,
+        message: String::from(message),
+    }
+}
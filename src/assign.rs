@@ -0,0 +1,93 @@
+use crate::{PressioConversionSafety, PressioError, PressioOption, PressioOptions};
+
+impl PressioOptions {
+    /// Parse command-line style `key=value` assignments into options.
+    ///
+    /// `self` provides the types of the options, e.g. the result of
+    /// [`PressioCompressor::get_options`](crate::PressioCompressor::get_options).
+    /// Each string value is cast to the type of the option with the same key
+    /// using [`PressioConversionSafety::Special`]. The returned options only
+    /// contain the assigned keys.
+    ///
+    /// An error is returned if an assignment is malformed, if its key does
+    /// not exist in `self`, or if its value cannot be cast.
+    pub fn parse_assignments<I: IntoIterator<Item = S>, S: AsRef<str>>(
+        &self,
+        assignments: I,
+    ) -> Result<Self, PressioError> {
+        let mut options = Self::new()?;
+
+        for assignment in assignments {
+            let assignment = assignment.as_ref();
+
+            let Some((key, value)) = assignment.split_once('=') else {
+                return Err(PressioError {
+                    error_code: 1,
+                    message: format!(
+                        "invalid option assignment `{assignment}`, expected `key=value`"
+                    ),
+                });
+            };
+            let key = key.trim();
+
+            let option_type = match self.get(key)? {
+                Some(option) => option.copy_type_only(),
+                None if self.has_option(key)? => {
+                    return Err(PressioError {
+                        error_code: 1,
+                        message: format!("option `{key}` has an unsupported type"),
+                    });
+                }
+                None => return Err(self.unknown_key_error(key)),
+            };
+
+            let value = PressioOption::string(Some(String::from(value)));
+
+            if let PressioOption::unset | PressioOption::string(_) = option_type {
+                options.set(key, value)?;
+                continue;
+            }
+
+            options.set(key, option_type.clone())?;
+            options
+                .set_with_cast(key, value, PressioConversionSafety::Special)
+                .map_err(|err| PressioError {
+                    error_code: err.error_code,
+                    message: format!(
+                        "failed to parse `{assignment}`: cannot convert the value to the {} type of option `{key}`",
+                        option_type.type_name(),
+                    ),
+                })?;
+        }
+
+        Ok(options)
+    }
+
+    fn unknown_key_error(&self, key: &str) -> PressioError {
+        let name = key.rsplit_once(':').map_or(key, |(_, name)| name);
+
+        let candidates = self
+            .iter()
+            .filter_map(|(candidate, _)| candidate)
+            .filter(|candidate| {
+                candidate
+                    .rsplit_once(':')
+                    .map_or(candidate.as_str(), |(_, name)| name)
+                    == name
+            })
+            .map(|candidate| format!("`{candidate}`"))
+            .collect::<Vec<_>>();
+
+        PressioError {
+            error_code: 1,
+            message: if candidates.is_empty() {
+                format!("no such option `{key}`")
+            } else {
+                format!(
+                    "no such option `{key}`, did you mean {}?",
+                    candidates.join(" or ")
+                )
+            },
+        }
+    }
+}
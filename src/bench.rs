@@ -0,0 +1,514 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{self, Write},
+    time::Instant,
+};
+
+use crate::{
+    Pressio, PressioCompressor, PressioConversionSafety, PressioData, PressioDtype, PressioError,
+    PressioOption, PressioOptions,
+};
+
+/// A grid of option values whose cartesian product is benchmarked.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OptionGrid {
+    axes: Vec<(String, Vec<PressioOption>)>,
+}
+
+impl OptionGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an axis with all `values` that the option `key` should take.
+    #[must_use]
+    pub fn axis(
+        mut self,
+        key: impl Into<String>,
+        values: impl IntoIterator<Item = PressioOption>,
+    ) -> Self {
+        self.axes.push((key.into(), values.into_iter().collect()));
+        self
+    }
+
+    /// Iterate over all points of the grid, each given as a list of
+    /// `(key, value)` pairs.
+    ///
+    /// An empty grid has a single point without any options. An error is
+    /// returned if an axis has no values, since the grid would then have no
+    /// points.
+    pub fn points(
+        &self,
+    ) -> Result<impl Iterator<Item = Vec<(String, PressioOption)>> + '_, PressioError> {
+        if let Some((key, _)) = self.axes.iter().find(|(_, values)| values.is_empty()) {
+            return Err(PressioError {
+                error_code: 1,
+                message: format!("option grid axis `{key}` has no values"),
+            });
+        }
+
+        let len = self
+            .axes
+            .iter()
+            .map(|(_, values)| values.len())
+            .product::<usize>();
+
+        Ok((0..len).map(|mut index| {
+            let mut point = Vec::with_capacity(self.axes.len());
+            for (key, values) in self.axes.iter().rev() {
+                point.push((key.clone(), values[index % values.len()].clone()));
+                index /= values.len();
+            }
+            point.reverse();
+            point
+        }))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchCase {
+    pub compressor_id: String,
+    pub grid: OptionGrid,
+}
+
+/// A benchmark of several compressors on the same data.
+///
+/// A [`Bench`] runs every [`BenchCase`], i.e. a compressor id together with an
+/// [`OptionGrid`], repeatedly on the same input data. Each run records the
+/// wall-clock timings and all numeric metrics that the compressor reports in
+/// [`PressioCompressor::get_metric_results`]. The resulting [`BenchReport`]
+/// can be printed as a table or written as CSV or JSON.
+///
+/// Every compressor is instantiated as the child of the `pressio`
+/// meta-compressor, which enables the [`Bench::metrics`] through the
+/// `composite` metrics plugin.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bench {
+    pub cases: Vec<BenchCase>,
+    pub repetitions: usize,
+    /// The ids of the libpressio metrics plugins that are enabled.
+    pub metrics: Vec<String>,
+}
+
+impl Default for Bench {
+    fn default() -> Self {
+        Self {
+            cases: Vec::new(),
+            repetitions: 3,
+            metrics: vec![String::from("size"), String::from("error_stat")],
+        }
+    }
+}
+
+impl Bench {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a benchmark of every compressor in
+    /// [`supported_compressors`](crate::supported_compressors) with its
+    /// default options.
+    pub fn all_compressors() -> Result<Self, PressioError> {
+        let mut bench = Self::new();
+        for id in crate::supported_compressors()? {
+            bench = bench.case(id, OptionGrid::new());
+        }
+        Ok(bench)
+    }
+
+    #[must_use]
+    pub fn case(mut self, compressor_id: impl Into<String>, grid: OptionGrid) -> Self {
+        self.cases.push(BenchCase {
+            compressor_id: compressor_id.into(),
+            grid,
+        });
+        self
+    }
+
+    /// Run all cases on the `input` data.
+    ///
+    /// A case that fails, e.g. because the compressor does not support the
+    /// dtype of the input, is recorded in [`BenchReport::failures`] and does
+    /// not stop the benchmark. An error is returned if the input has no dtype
+    /// or if an [`OptionGrid`] has an axis without values.
+    pub fn run(&self, lib: &mut Pressio, input: &PressioData) -> Result<BenchReport, PressioError> {
+        let Some(dtype) = input.dtype() else {
+            return Err(PressioError {
+                error_code: 1,
+                message: String::from("cannot benchmark data with an unknown dtype"),
+            });
+        };
+        let shape = input.shape();
+
+        let mut report = BenchReport::default();
+
+        for case in &self.cases {
+            for point in case.grid.points()? {
+                let options = point
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.to_string()))
+                    .collect::<Vec<_>>();
+
+                let result = self.run_point(
+                    lib,
+                    &case.compressor_id,
+                    &point,
+                    |repetition, compressor| {
+                        let compress_start = Instant::now();
+                        let compressed = compressor
+                            .compress(input, PressioData::new_empty(PressioDtype::Byte, []))?;
+                        let compress_seconds = compress_start.elapsed().as_secs_f64();
+
+                        let decompress_start = Instant::now();
+                        let _decompressed = compressor
+                            .decompress(&compressed, PressioData::new_empty(dtype, &shape))?;
+                        let decompress_seconds = decompress_start.elapsed().as_secs_f64();
+
+                        let metrics = compressor
+                            .get_metric_results()?
+                            .iter()
+                            .filter_map(|(key, value)| Some((key?, value?.as_f64()?)))
+                            .collect::<BTreeMap<_, _>>();
+
+                        let compression_ratio = metrics
+                            .get("size:compression_ratio")
+                            .copied()
+                            .unwrap_or(input.num_bytes() as f64 / compressed.num_bytes() as f64);
+
+                        let num_bytes = input.num_bytes() as f64;
+
+                        report.records.push(BenchRecord {
+                            compressor_id: case.compressor_id.clone(),
+                            options: options.clone(),
+                            repetition,
+                            compress_seconds,
+                            decompress_seconds,
+                            compress_throughput: num_bytes / compress_seconds,
+                            decompress_throughput: num_bytes / decompress_seconds,
+                            compression_ratio,
+                            metrics,
+                        });
+
+                        Ok(())
+                    },
+                );
+
+                if let Err(error) = result {
+                    report.failures.push(BenchFailure {
+                        compressor_id: case.compressor_id.clone(),
+                        options,
+                        error,
+                    });
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn run_point(
+        &self,
+        lib: &mut Pressio,
+        compressor_id: &str,
+        point: &[(String, PressioOption)],
+        mut repetition: impl FnMut(usize, &mut PressioCompressor) -> Result<(), PressioError>,
+    ) -> Result<(), PressioError> {
+        let mut compressor = lib.get_compressor("pressio")?;
+
+        let mut setup = PressioOptions::new()?;
+        setup.set(
+            "pressio:compressor",
+            PressioOption::string(Some(String::from(compressor_id))),
+        )?;
+        if !self.metrics.is_empty() {
+            setup.set(
+                "pressio:metric",
+                PressioOption::string(Some(String::from("composite"))),
+            )?;
+            setup.set(
+                "composite:plugins",
+                PressioOption::vec_string(Some(self.metrics.clone())),
+            )?;
+        }
+        compressor.set_options(&setup)?;
+
+        // the options of the grid point are set only after the compressor is
+        //  selected, since they may belong to the selected compressor
+        let template = compressor.get_options()?;
+        let mut options = PressioOptions::new()?;
+        for (key, value) in point {
+            match template.get(key)? {
+                Some(option_type) => {
+                    options.set(key, option_type.copy_type_only())?;
+                    options.set_with_cast(key, value.clone(), PressioConversionSafety::Special)?;
+                }
+                None => options.set(key, value.clone())?,
+            }
+        }
+        compressor.set_options(&options)?;
+
+        for i in 0..self.repetitions {
+            repetition(i, &mut compressor)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The measurements of a single repetition of a benchmark case.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchRecord {
+    pub compressor_id: String,
+    /// The options of the grid point, formatted as `(key, value)` pairs.
+    pub options: Vec<(String, String)>,
+    pub repetition: usize,
+    pub compress_seconds: f64,
+    pub decompress_seconds: f64,
+    /// Uncompressed bytes compressed per second.
+    pub compress_throughput: f64,
+    /// Uncompressed bytes decompressed per second.
+    pub decompress_throughput: f64,
+    pub compression_ratio: f64,
+    /// All numeric metric results, e.g. `error_stat:psnr`.
+    pub metrics: BTreeMap<String, f64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct BenchFailure {
+    pub compressor_id: String,
+    pub options: Vec<(String, String)>,
+    pub error: PressioError,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct BenchReport {
+    pub records: Vec<BenchRecord>,
+    pub failures: Vec<BenchFailure>,
+}
+
+impl BenchReport {
+    /// All metric keys that are reported by any record, in sorted order.
+    pub fn metric_keys(&self) -> BTreeSet<&str> {
+        self.records
+            .iter()
+            .flat_map(|record| record.metrics.keys().map(String::as_str))
+            .collect()
+    }
+
+    /// Write the records as CSV, with one column per metric key.
+    ///
+    /// Metrics that a record does not report are left empty.
+    pub fn write_csv(&self, mut writer: impl Write) -> io::Result<()> {
+        let metric_keys = self.metric_keys();
+
+        write!(
+            writer,
+            "compressor,options,repetition,compress_seconds,decompress_seconds,compress_throughput,decompress_throughput,compression_ratio"
+        )?;
+        for key in &metric_keys {
+            write!(writer, ",{}", csv_escape(key))?;
+        }
+        writeln!(writer)?;
+
+        for record in &self.records {
+            write!(
+                writer,
+                "{},{},{},{},{},{},{},{}",
+                csv_escape(&record.compressor_id),
+                csv_escape(&format_options(&record.options)),
+                record.repetition,
+                record.compress_seconds,
+                record.decompress_seconds,
+                record.compress_throughput,
+                record.decompress_throughput,
+                record.compression_ratio,
+            )?;
+            for key in &metric_keys {
+                match record.metrics.get(*key) {
+                    Some(value) => write!(writer, ",{value}")?,
+                    None => write!(writer, ",")?,
+                }
+            }
+            writeln!(writer)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the records and failures as a JSON object.
+    ///
+    /// Non-finite numbers are written as `null`.
+    pub fn write_json(&self, mut writer: impl Write) -> io::Result<()> {
+        writeln!(writer, "{{")?;
+        writeln!(writer, "  \"records\": [")?;
+        for (i, record) in self.records.iter().enumerate() {
+            write!(
+                writer,
+                "    {{\"compressor\": {}, \"options\": {}, \"repetition\": {}, \"compress_seconds\": {}, \"decompress_seconds\": {}, \"compress_throughput\": {}, \"decompress_throughput\": {}, \"compression_ratio\": {}, \"metrics\": {{",
+                json_string(&record.compressor_id),
+                json_options(&record.options),
+                record.repetition,
+                json_number(record.compress_seconds),
+                json_number(record.decompress_seconds),
+                json_number(record.compress_throughput),
+                json_number(record.decompress_throughput),
+                json_number(record.compression_ratio),
+            )?;
+            for (j, (key, value)) in record.metrics.iter().enumerate() {
+                let separator = if j == 0 { "" } else { ", " };
+                write!(
+                    writer,
+                    "{separator}{}: {}",
+                    json_string(key),
+                    json_number(*value)
+                )?;
+            }
+            let separator = if i + 1 == self.records.len() { "" } else { "," };
+            writeln!(writer, "}}}}{separator}")?;
+        }
+        writeln!(writer, "  ],")?;
+        writeln!(writer, "  \"failures\": [")?;
+        for (i, failure) in self.failures.iter().enumerate() {
+            let separator = if i + 1 == self.failures.len() {
+                ""
+            } else {
+                ","
+            };
+            writeln!(
+                writer,
+                "    {{\"compressor\": {}, \"options\": {}, \"error_code\": {}, \"message\": {}}}{separator}",
+                json_string(&failure.compressor_id),
+                json_options(&failure.options),
+                failure.error.error_code,
+                json_string(&failure.error.message),
+            )?;
+        }
+        writeln!(writer, "  ]")?;
+        writeln!(writer, "}}")
+    }
+}
+
+impl std::fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let header = [
+            "compressor",
+            "options",
+            "rep",
+            "ratio",
+            "compress MB/s",
+            "decompress MB/s",
+            "psnr",
+            "max error",
+        ];
+
+        let rows = self
+            .records
+            .iter()
+            .map(|record| {
+                let metric = |key| {
+                    record
+                        .metrics
+                        .get(key)
+                        .map_or_else(String::new, |value| format!("{value:.3}"))
+                };
+                [
+                    record.compressor_id.clone(),
+                    format_options(&record.options),
+                    record.repetition.to_string(),
+                    format!("{:.3}", record.compression_ratio),
+                    format!("{:.1}", record.compress_throughput / 1e6),
+                    format!("{:.1}", record.decompress_throughput / 1e6),
+                    metric("error_stat:psnr"),
+                    metric("error_stat:max_error"),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let mut widths = header.map(str::len);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+
+        for (i, (cell, width)) in header.iter().zip(widths).enumerate() {
+            let separator = if i == 0 { "" } else { " | " };
+            write!(f, "{separator}{cell:<width$}")?;
+        }
+        writeln!(f)?;
+        for (i, width) in widths.iter().enumerate() {
+            let separator = if i == 0 { "" } else { "-|-" };
+            write!(f, "{separator}{}", "-".repeat(*width))?;
+        }
+        writeln!(f)?;
+        for row in &rows {
+            for (i, (cell, width)) in row.iter().zip(widths).enumerate() {
+                let separator = if i == 0 { "" } else { " | " };
+                write!(f, "{separator}{cell:<width$}")?;
+            }
+            writeln!(f)?;
+        }
+
+        for failure in &self.failures {
+            writeln!(
+                f,
+                "failed: {} {}: {}",
+                failure.compressor_id,
+                format_options(&failure.options),
+                failure.error
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn format_options(options: &[(String, String)]) -> String {
+    options
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        String::from(value)
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if u32::from(c) < 0x20 => escaped.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        format!("{value}")
+    } else {
+        String::from("null")
+    }
+}
+
+fn json_options(options: &[(String, String)]) -> String {
+    let entries = options
+        .iter()
+        .map(|(key, value)| format!("{}: {}", json_string(key), json_string(value)))
+        .collect::<Vec<_>>();
+    format!("{{{}}}", entries.join(", "))
+}
@@ -0,0 +1,439 @@
+//! Command-line interface to compress and decompress files with libpressio.
+
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use libpressio::{
+    CompressorSchema, Pressio, PressioCompressor, PressioData, PressioDtype, PressioError,
+    PressioOption, PressioOptionKind, PressioOptions,
+};
+
+#[derive(Parser)]
+#[command(version, about = "Compress and decompress files with libpressio")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List the compressors, io modules, metrics, and features of libpressio
+    List {
+        /// Only list one kind of plugin
+        kind: Option<ListKind>,
+    },
+    /// Print the documented options of a compressor
+    Doc {
+        /// The compressor id, e.g. `sz3`
+        id: String,
+    },
+    /// Compress a raw or `.npy` file
+    Compress {
+        #[command(flatten)]
+        compressor: CompressorArgs,
+        #[command(flatten)]
+        layout: LayoutArgs,
+        input: PathBuf,
+        output: PathBuf,
+    },
+    /// Decompress a file into a raw or `.npy` file
+    Decompress {
+        #[command(flatten)]
+        compressor: CompressorArgs,
+        #[command(flatten)]
+        layout: LayoutArgs,
+        input: PathBuf,
+        output: PathBuf,
+    },
+    /// Compress and decompress a raw or `.npy` file and print the metrics
+    Metrics {
+        #[command(flatten)]
+        compressor: CompressorArgs,
+        #[command(flatten)]
+        layout: LayoutArgs,
+        input: PathBuf,
+    },
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum ListKind {
+    Compressors,
+    Io,
+    Metrics,
+    Features,
+}
+
+#[derive(Args)]
+struct CompressorArgs {
+    /// The compressor id, e.g. `sz3`
+    #[arg(short, long)]
+    compressor: String,
+    /// Set a compressor option, e.g. `-o pressio:abs=1e-3`
+    #[arg(short = 'o', long = "option", value_name = "KEY=VALUE")]
+    options: Vec<String>,
+    /// Enable a metric, e.g. `-m size -m error_stat`
+    #[arg(short, long = "metric", value_name = "ID")]
+    metrics: Vec<String>,
+}
+
+#[derive(Args)]
+struct LayoutArgs {
+    /// The dtype of raw uncompressed data
+    #[arg(short, long)]
+    dtype: Option<Dtype>,
+    /// The dimensions of raw uncompressed data, e.g. `--dim 100 --dim 500`
+    #[arg(long = "dim", value_name = "LEN")]
+    dims: Vec<usize>,
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum Dtype {
+    Bool,
+    Byte,
+    #[value(alias = "u8")]
+    Uint8,
+    #[value(alias = "u16")]
+    Uint16,
+    #[value(alias = "u32")]
+    Uint32,
+    #[value(alias = "u64")]
+    Uint64,
+    #[value(alias = "i8")]
+    Int8,
+    #[value(alias = "i16")]
+    Int16,
+    #[value(alias = "i32")]
+    Int32,
+    #[value(alias = "i64")]
+    Int64,
+    #[value(alias = "f32")]
+    Float,
+    #[value(alias = "f64")]
+    Double,
+}
+
+impl From<Dtype> for PressioDtype {
+    fn from(dtype: Dtype) -> Self {
+        match dtype {
+            Dtype::Bool => Self::Bool,
+            Dtype::Byte => Self::Byte,
+            Dtype::Uint8 => Self::U8,
+            Dtype::Uint16 => Self::U16,
+            Dtype::Uint32 => Self::U32,
+            Dtype::Uint64 => Self::U64,
+            Dtype::Int8 => Self::I8,
+            Dtype::Int16 => Self::I16,
+            Dtype::Int32 => Self::I32,
+            Dtype::Int64 => Self::I64,
+            Dtype::Float => Self::F32,
+            Dtype::Double => Self::F64,
+        }
+    }
+}
+
+impl LayoutArgs {
+    fn template(&self) -> Result<PressioData, PressioError> {
+        match self.dtype {
+            Some(dtype) if !self.dims.is_empty() => {
+                Ok(PressioData::new_empty(dtype.into(), &self.dims))
+            }
+            _ => Err(PressioError {
+                error_code: 1,
+                message: String::from("raw data requires both `--dtype` and `--dim`"),
+            }),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), PressioError> {
+    let mut lib = Pressio::new()?;
+
+    match cli.command {
+        Command::List { kind } => list(kind),
+        Command::Doc { id } => doc(&mut lib, &id),
+        Command::Compress {
+            compressor,
+            layout,
+            input,
+            output,
+        } => {
+            let input = read_uncompressed(&mut lib, &input, &layout)?;
+            let mut compressor = compressor.instantiate(&mut lib)?;
+            let compressed =
+                compressor.compress(&input, PressioData::new_empty(PressioDtype::Byte, []))?;
+            write(&mut lib, &output, &compressed, false)?;
+            print_metrics(&compressor)
+        }
+        Command::Decompress {
+            compressor,
+            layout,
+            input,
+            output,
+        } => {
+            let compressed = read(&mut lib, &input, None)?;
+            let mut compressor = compressor.instantiate(&mut lib)?;
+            let decompressed = compressor.decompress(&compressed, layout.template()?)?;
+            write(&mut lib, &output, &decompressed, true)?;
+            print_metrics(&compressor)
+        }
+        Command::Metrics {
+            compressor,
+            layout,
+            input,
+        } => {
+            let input = read_uncompressed(&mut lib, &input, &layout)?;
+            let mut compressor = compressor.instantiate(&mut lib)?;
+            let compressed =
+                compressor.compress(&input, PressioData::new_empty(PressioDtype::Byte, []))?;
+            let decompressed =
+                PressioData::new_empty(input.dtype().unwrap_or(PressioDtype::Byte), input.shape());
+            compressor.decompress(&compressed, decompressed)?;
+            print_metrics(&compressor)
+        }
+    }
+}
+
+fn list(kind: Option<ListKind>) -> Result<(), PressioError> {
+    let kinds = match kind {
+        Some(kind) => vec![kind],
+        None => vec![
+            ListKind::Compressors,
+            ListKind::Io,
+            ListKind::Metrics,
+            ListKind::Features,
+        ],
+    };
+
+    for kind in &kinds {
+        let (title, ids) = match kind {
+            ListKind::Compressors => ("compressors", libpressio::supported_compressors()?),
+            ListKind::Io => ("io modules", libpressio::supported_io_modules()?),
+            ListKind::Metrics => ("metrics", libpressio::supported_metrics()?),
+            ListKind::Features => ("features", libpressio::features()?),
+        };
+
+        // a single kind is listed without a title to simplify scripting
+        if kinds.len() == 1 {
+            ids.iter().for_each(|id| println!("{id}"));
+        } else {
+            println!("{title}:");
+            ids.iter().for_each(|id| println!("  {id}"));
+        }
+    }
+
+    Ok(())
+}
+
+fn doc(lib: &mut Pressio, id: &str) -> Result<(), PressioError> {
+    let compressor = lib.get_compressor(id)?;
+    let schema = CompressorSchema::from_compressor(&compressor)?;
+
+    println!("{}", schema.prefix);
+    if let Some(description) = &schema.description {
+        println!("  {description}");
+    }
+
+    for (title, kind) in [
+        ("options", PressioOptionKind::Tunable),
+        ("configuration", PressioOptionKind::Configuration),
+    ] {
+        let entries = schema
+            .entries()
+            .filter(|entry| entry.kind == kind)
+            .collect::<Vec<_>>();
+        if entries.is_empty() {
+            continue;
+        }
+
+        println!("\n{title}:");
+        for entry in entries {
+            if entry.default == entry.option_type {
+                println!("  {} <{}>", entry.key, entry.option_type.type_name());
+            } else {
+                println!(
+                    "  {} <{}> = {}",
+                    entry.key,
+                    entry.option_type.type_name(),
+                    entry.default
+                );
+            }
+            if let Some(doc) = &entry.doc {
+                println!("      {doc}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl CompressorArgs {
+    /// Instantiate the compressor as a child of the `pressio` meta-compressor,
+    /// which enables the metrics through the `composite` metrics plugin.
+    fn instantiate(&self, lib: &mut Pressio) -> Result<PressioCompressor, PressioError> {
+        let mut compressor = lib.get_compressor("pressio")?;
+
+        let mut setup = PressioOptions::new()?;
+        setup.set(
+            "pressio:compressor",
+            PressioOption::string(Some(self.compressor.clone())),
+        )?;
+        if !self.metrics.is_empty() {
+            setup.set(
+                "pressio:metric",
+                PressioOption::string(Some(String::from("composite"))),
+            )?;
+            setup.set(
+                "composite:plugins",
+                PressioOption::vec_string(Some(self.metrics.clone())),
+            )?;
+        }
+        compressor.set_options(&setup)?;
+
+        // the options are parsed only after the compressor is selected, since
+        //  they may belong to the selected compressor
+        let options = compressor.get_options()?.parse_assignments(&self.options)?;
+        compressor.set_options(&options)?;
+
+        Ok(compressor)
+    }
+}
+
+fn print_metrics(compressor: &PressioCompressor) -> Result<(), PressioError> {
+    let results = compressor.get_metric_results()?;
+    for entry in &results {
+        let (key, value) = entry?;
+        println!("{key} = {value}");
+    }
+    Ok(())
+}
+
+fn is_npy(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "npy")
+}
+
+fn read_uncompressed(
+    lib: &mut Pressio,
+    path: &Path,
+    layout: &LayoutArgs,
+) -> Result<PressioData, PressioError> {
+    if is_npy(path) {
+        PressioData::read_npy(path)
+    } else {
+        read(lib, path, Some(layout.template()?))
+    }
+}
+
+fn read(
+    lib: &mut Pressio,
+    path: &Path,
+    template: Option<PressioData>,
+) -> Result<PressioData, PressioError> {
+    let mut io = lib.get_io("posix")?;
+    io.set_options(&io_path(path)?)?;
+    io.read(template)
+}
+
+/// Write the `data` as raw bytes, or as an `.npy` file if `npy` is enabled
+/// and the `path` has the `.npy` extension.
+fn write(
+    lib: &mut Pressio,
+    path: &Path,
+    data: &PressioData,
+    npy: bool,
+) -> Result<(), PressioError> {
+    if npy && is_npy(path) {
+        return data.write_npy(path);
+    }
+
+    let mut io = lib.get_io("posix")?;
+    io.set_options(&io_path(path)?)?;
+    io.write(data)
+}
+
+fn io_path(path: &Path) -> Result<PressioOptions, PressioError> {
+    let Some(path) = path.to_str() else {
+        return Err(PressioError {
+            error_code: 2,
+            message: format!("invalid UTF-8 in path {}", path.display()),
+        });
+    };
+
+    let mut options = PressioOptions::new()?;
+    options.set("io:path", PressioOption::string(Some(String::from(path))))?;
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compress_decompress_smoke() -> Result<(), PressioError> {
+        let dir = std::env::temp_dir().join(format!("libpressio-cli-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).map_err(|err| PressioError {
+            error_code: 1,
+            message: err.to_string(),
+        })?;
+        let input = dir.join("input.npy");
+        let compressed = dir.join("compressed.bin");
+        let output = dir.join("output.npy");
+
+        let data = PressioData::new_copied(ndarray::Array2::from_shape_fn((4, 5), |(i, j)| {
+            (i * 5 + j) as f32
+        }));
+        data.write_npy(&input)?;
+
+        let cli = |args: &[&std::ffi::OsStr]| {
+            Cli::try_parse_from(
+                [std::ffi::OsStr::new("pressio")]
+                    .iter()
+                    .chain(args)
+                    .copied(),
+            )
+            .map_err(|err| PressioError {
+                error_code: 1,
+                message: err.to_string(),
+            })
+        };
+
+        run(cli(&[
+            "compress".as_ref(),
+            "-c".as_ref(),
+            "noop".as_ref(),
+            input.as_os_str(),
+            compressed.as_os_str(),
+        ])?)?;
+        run(cli(&[
+            "decompress".as_ref(),
+            "-c".as_ref(),
+            "noop".as_ref(),
+            "--dtype".as_ref(),
+            "f32".as_ref(),
+            "--dim".as_ref(),
+            "4".as_ref(),
+            "--dim".as_ref(),
+            "5".as_ref(),
+            compressed.as_os_str(),
+            output.as_os_str(),
+        ])?)?;
+
+        assert_eq!(PressioData::read_npy(&output)?, data);
+
+        let _ = std::fs::remove_dir_all(&dir);
+
+        Ok(())
+    }
+}
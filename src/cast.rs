@@ -0,0 +1,238 @@
+use crate::{PressioArray, PressioData, PressioDtype, PressioError};
+
+/// What happens to values that are out of range for the target dtype of
+/// [`PressioData::try_cast`], or NaN when casting to an integer dtype.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CastOverflow {
+    /// Out-of-range values are clamped to the nearest representable value,
+    /// and NaN is converted to zero.
+    #[default]
+    Saturate,
+    /// The cast fails on the first out-of-range or NaN value.
+    Error,
+}
+
+/// How floating-point values are rounded when they are cast to an integer
+/// dtype by [`PressioData::try_cast`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CastRounding {
+    /// Truncate the fractional part, like an `as` cast.
+    #[default]
+    TowardZero,
+    /// Round to the nearest integer, with ties rounded to even.
+    Nearest,
+    /// Round towards negative infinity.
+    Floor,
+    /// Round towards positive infinity.
+    Ceil,
+}
+
+/// The policy for a range-checked [`PressioData::try_cast`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CastPolicy {
+    pub overflow: CastOverflow,
+    pub rounding: CastRounding,
+}
+
+/// How many elements were changed by a [`PressioData::try_cast`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CastReport {
+    pub num_elements: usize,
+    /// The number of out-of-range elements that were clamped.
+    pub num_clamped: usize,
+    /// The number of NaN elements that were converted to zero.
+    pub num_nan: usize,
+    /// The number of elements with a fractional part that was rounded away.
+    pub num_rounded: usize,
+}
+
+impl CastReport {
+    /// Whether the cast preserved the value of every element.
+    pub fn is_exact(&self) -> bool {
+        self.num_clamped == 0 && self.num_nan == 0 && self.num_rounded == 0
+    }
+}
+
+impl PressioData {
+    /// Cast the data to the `dtype`, with range checks and rounding
+    /// according to the `policy`.
+    ///
+    /// Unlike [`PressioData::cast`], values that are out of range for the
+    /// `dtype` never wrap around. Casts to [`PressioDtype::Bool`] treat it
+    /// as an integer dtype with the range `0..=1`. Casting integers to
+    /// floating-point dtypes may round them to the nearest representable
+    /// value, which is not reported.
+    ///
+    /// An error is returned if the data has no values, or if the policy
+    /// uses [`CastOverflow::Error`] and an element is out of range or NaN.
+    pub fn try_cast(
+        &self,
+        dtype: PressioDtype,
+        policy: CastPolicy,
+    ) -> Result<(Self, CastReport), PressioError> {
+        let Some(array) = self.clone_into_array() else {
+            return Err(cast_error(String::from("cannot cast data without values")));
+        };
+
+        let mut report = CastReport {
+            num_elements: self.len(),
+            ..CastReport::default()
+        };
+        let mut bytes = Vec::with_capacity(self.len() * dtype.size_in_bytes());
+
+        for (index, value) in values(&array).enumerate() {
+            let value = cast_value(value, dtype, policy, &mut report).ok_or_else(|| {
+                cast_error(format!(
+                    "element {index} with value {value} cannot be represented as {dtype}"
+                ))
+            })?;
+            value.write_ne_bytes(dtype, &mut bytes);
+        }
+
+        Ok((Self::from_bytes(dtype, &self.shape(), &bytes), report))
+    }
+}
+
+/// A value of any dtype, which can be represented losslessly.
+#[derive(Copy, Clone, Debug)]
+enum Value {
+    Int(i128),
+    Float(f64),
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(x) => x.fmt(f),
+            Self::Float(x) => x.fmt(f),
+        }
+    }
+}
+
+impl Value {
+    fn write_ne_bytes(self, dtype: PressioDtype, bytes: &mut Vec<u8>) {
+        // the value has already been range-checked for the dtype
+        match (self, dtype) {
+            (Self::Int(x), PressioDtype::Bool | PressioDtype::Byte | PressioDtype::U8) => {
+                bytes.push(x as u8);
+            }
+            (Self::Int(x), PressioDtype::U16) => bytes.extend((x as u16).to_ne_bytes()),
+            (Self::Int(x), PressioDtype::U32) => bytes.extend((x as u32).to_ne_bytes()),
+            (Self::Int(x), PressioDtype::U64) => bytes.extend((x as u64).to_ne_bytes()),
+            (Self::Int(x), PressioDtype::I8) => bytes.extend((x as i8).to_ne_bytes()),
+            (Self::Int(x), PressioDtype::I16) => bytes.extend((x as i16).to_ne_bytes()),
+            (Self::Int(x), PressioDtype::I32) => bytes.extend((x as i32).to_ne_bytes()),
+            (Self::Int(x), PressioDtype::I64) => bytes.extend((x as i64).to_ne_bytes()),
+            (Self::Int(x), PressioDtype::F32) => bytes.extend((x as f32).to_ne_bytes()),
+            (Self::Int(x), PressioDtype::F64) => bytes.extend((x as f64).to_ne_bytes()),
+            (Self::Float(x), PressioDtype::F32) => bytes.extend((x as f32).to_ne_bytes()),
+            (Self::Float(x), PressioDtype::F64) => bytes.extend(x.to_ne_bytes()),
+            (Self::Float(_), _) => unreachable!("floats are converted to integers before"),
+        }
+    }
+}
+
+fn values(array: &PressioArray) -> Box<dyn Iterator<Item = Value> + '_> {
+    match array {
+        PressioArray::Byte(a) | PressioArray::U8(a) => {
+            Box::new(a.iter().map(|&x| Value::Int(i128::from(x))))
+        }
+        PressioArray::Bool(a) => Box::new(a.iter().map(|&x| Value::Int(i128::from(x)))),
+        PressioArray::U16(a) => Box::new(a.iter().map(|&x| Value::Int(i128::from(x)))),
+        PressioArray::U32(a) => Box::new(a.iter().map(|&x| Value::Int(i128::from(x)))),
+        PressioArray::U64(a) => Box::new(a.iter().map(|&x| Value::Int(i128::from(x)))),
+        PressioArray::I8(a) => Box::new(a.iter().map(|&x| Value::Int(i128::from(x)))),
+        PressioArray::I16(a) => Box::new(a.iter().map(|&x| Value::Int(i128::from(x)))),
+        PressioArray::I32(a) => Box::new(a.iter().map(|&x| Value::Int(i128::from(x)))),
+        PressioArray::I64(a) => Box::new(a.iter().map(|&x| Value::Int(i128::from(x)))),
+        PressioArray::F32(a) => Box::new(a.iter().map(|&x| Value::Float(f64::from(x)))),
+        PressioArray::F64(a) => Box::new(a.iter().map(|&x| Value::Float(x))),
+    }
+}
+
+/// The inclusive range of an integer dtype, or [`None`] for floating-point
+/// dtypes.
+const fn integer_range(dtype: PressioDtype) -> Option<(i128, i128)> {
+    match dtype {
+        PressioDtype::Bool => Some((0, 1)),
+        PressioDtype::Byte | PressioDtype::U8 => Some((0, u8::MAX as i128)),
+        PressioDtype::U16 => Some((0, u16::MAX as i128)),
+        PressioDtype::U32 => Some((0, u32::MAX as i128)),
+        PressioDtype::U64 => Some((0, u64::MAX as i128)),
+        PressioDtype::I8 => Some((i8::MIN as i128, i8::MAX as i128)),
+        PressioDtype::I16 => Some((i16::MIN as i128, i16::MAX as i128)),
+        PressioDtype::I32 => Some((i32::MIN as i128, i32::MAX as i128)),
+        PressioDtype::I64 => Some((i64::MIN as i128, i64::MAX as i128)),
+        PressioDtype::F32 | PressioDtype::F64 => None,
+    }
+}
+
+/// Cast the `value` to the `dtype` and record any changes in the `report`,
+/// or return [`None`] if the value is out of range and the policy does not
+/// allow clamping it.
+fn cast_value(
+    value: Value,
+    dtype: PressioDtype,
+    policy: CastPolicy,
+    report: &mut CastReport,
+) -> Option<Value> {
+    let saturate = policy.overflow == CastOverflow::Saturate;
+
+    let Some((min, max)) = integer_range(dtype) else {
+        return match value {
+            // only finite doubles can be out of range for floats
+            Value::Float(x)
+                if dtype == PressioDtype::F32 && x.is_finite() && x.abs() > f64::from(f32::MAX) =>
+            {
+                if !saturate {
+                    return None;
+                }
+                report.num_clamped += 1;
+                Some(Value::Float(f64::from(f32::MAX).copysign(x)))
+            }
+            value => Some(value),
+        };
+    };
+
+    let x = match value {
+        Value::Int(x) => x,
+        Value::Float(x) if x.is_nan() => {
+            if !saturate {
+                return None;
+            }
+            report.num_nan += 1;
+            return Some(Value::Int(0));
+        }
+        Value::Float(x) => {
+            let rounded = match policy.rounding {
+                CastRounding::TowardZero => x.trunc(),
+                CastRounding::Nearest => x.round_ties_even(),
+                CastRounding::Floor => x.floor(),
+                CastRounding::Ceil => x.ceil(),
+            };
+            if rounded != x {
+                report.num_rounded += 1;
+            }
+            // the float-to-int cast saturates at the i128 range, which is
+            //  wider than that of all dtypes
+            rounded as i128
+        }
+    };
+
+    if x < min || x > max {
+        if !saturate {
+            return None;
+        }
+        report.num_clamped += 1;
+        return Some(Value::Int(x.clamp(min, max)));
+    }
+
+    Some(Value::Int(x))
+}
+
+fn cast_error(message: String) -> PressioError {
+    PressioError {
+        error_code: 1,
+        message,
+    }
+}
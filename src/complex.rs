@@ -0,0 +1,90 @@
+use std::borrow::Borrow;
+
+use ndarray::{Array, ArrayBase, ArrayView, CowArray, Data, Dimension, IxDyn};
+use num_complex::Complex;
+
+use crate::{PressioData, PressioElement};
+
+/// A real floating-point type whose [`Complex`] numbers can be stored in
+/// [`PressioData`].
+pub trait ComplexElement: PressioElement + sealed::ComplexElement {}
+
+mod sealed {
+    pub trait ComplexElement {}
+}
+
+impl sealed::ComplexElement for f32 {}
+impl ComplexElement for f32 {}
+
+impl sealed::ComplexElement for f64 {}
+impl ComplexElement for f64 {}
+
+impl PressioData {
+    /// Copy an array of complex numbers into new data, which has an extra
+    /// trailing dimension of size two for the interleaved real and imaginary
+    /// parts.
+    pub fn new_copied_complex<T: ComplexElement, S: Data<Elem = Complex<T>>, D: Dimension>(
+        x: impl Borrow<ArrayBase<S, D>>,
+    ) -> Self {
+        let x = x.borrow().as_standard_layout();
+        Self::new_copied(complex_parts(&x))
+    }
+
+    /// Share an array of complex numbers with libpressio as data, which has
+    /// an extra trailing dimension of size two for the interleaved real and
+    /// imaginary parts, while `with` is called.
+    ///
+    /// The array is only copied if it is not in standard layout.
+    pub fn new_with_shared_complex<
+        T: ComplexElement,
+        S: Data<Elem = Complex<T>>,
+        D: Dimension,
+        O,
+    >(
+        x: impl Borrow<ArrayBase<S, D>>,
+        with: impl for<'a> FnOnce(&'a Self) -> O,
+    ) -> O {
+        let x = x.borrow().as_standard_layout();
+        Self::new_with_shared(complex_parts(&x), with)
+    }
+
+    /// Copy data with a trailing dimension of size two, which contains the
+    /// interleaved real and imaginary parts, into an array of complex numbers
+    /// without the trailing dimension.
+    ///
+    /// Returns [`None`] if the data has no values, if its dtype is not `T`,
+    /// or if its last dimension does not have size two.
+    pub fn clone_into_complex_array<T: ComplexElement>(&self) -> Option<Array<Complex<T>, IxDyn>> {
+        let shape = self.shape();
+        let (&2, outer) = shape.split_last()? else {
+            return None;
+        };
+
+        self.with_shared(IxDyn(&shape), |parts: CowArray<T, IxDyn>| {
+            let values = parts
+                .iter()
+                .step_by(2)
+                .zip(parts.iter().skip(1).step_by(2))
+                .map(|(&re, &im)| Complex::new(re, im))
+                .collect();
+            Array::from_shape_vec(IxDyn(outer), values)
+        })?
+        .ok()
+    }
+}
+
+/// View a standard layout array of complex numbers as an array of their
+/// interleaved real and imaginary parts, with an extra trailing dimension.
+fn complex_parts<'a, T: ComplexElement, D: Dimension>(
+    x: &'a CowArray<'_, Complex<T>, D>,
+) -> ArrayView<'a, T, IxDyn> {
+    debug_assert!(x.is_standard_layout());
+
+    let mut shape = x.shape().to_vec();
+    shape.push(2);
+
+    // SAFETY: Complex<T> is repr(C) with the real and imaginary parts as its
+    //         only fields, so a standard layout array of complex numbers is a
+    //         standard layout array of its parts
+    unsafe { ArrayView::from_shape_ptr(IxDyn(&shape), x.as_ptr().cast::<T>()) }
+}
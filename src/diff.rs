@@ -0,0 +1,82 @@
+use std::collections::BTreeMap;
+
+use crate::{PressioOption, PressioOptions};
+
+/// The differences between two [`PressioOptions`], sorted by key.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OptionsDiff {
+    /// Options that only exist in the new options.
+    pub added: Vec<(String, PressioOption)>,
+    /// Options that only exist in the old options.
+    pub removed: Vec<(String, PressioOption)>,
+    /// Options that exist in both but have different types or values.
+    pub changed: Vec<OptionChange>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptionChange {
+    pub key: String,
+    pub old: PressioOption,
+    pub new: PressioOption,
+}
+
+impl OptionsDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.added.len() + self.removed.len() + self.changed.len()
+    }
+}
+
+impl std::fmt::Display for OptionsDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (key, option) in &self.added {
+            writeln!(f, "+ {key} = {option:?}")?;
+        }
+        for (key, option) in &self.removed {
+            writeln!(f, "- {key} = {option:?}")?;
+        }
+        for OptionChange { key, old, new } in &self.changed {
+            writeln!(f, "~ {key} = {old:?} -> {new:?}")?;
+        }
+        Ok(())
+    }
+}
+
+impl PressioOptions {
+    /// Compute the differences from `self` (the old options) to `other` (the
+    /// new options).
+    ///
+    /// Options with non-UTF-8 keys or unsupported types are ignored.
+    pub fn diff(&self, other: &Self) -> OptionsDiff {
+        let mut old = self.to_btree_map();
+        let new = other.to_btree_map();
+
+        let mut diff = OptionsDiff::default();
+
+        for (key, new) in new {
+            match old.remove(&key) {
+                None => diff.added.push((key, new)),
+                Some(old) if old != new => diff.changed.push(OptionChange { key, old, new }),
+                Some(_) => (),
+            }
+        }
+        diff.removed.extend(old);
+
+        diff
+    }
+
+    fn to_btree_map(&self) -> BTreeMap<String, PressioOption> {
+        self.iter()
+            .filter_map(|(key, option)| Some((key?, option?)))
+            .collect()
+    }
+}
+
+impl PartialEq for PressioOptions {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.diff(other).is_empty()
+    }
+}
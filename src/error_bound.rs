@@ -0,0 +1,130 @@
+use crate::{
+    PressioCompressor, PressioConversionSafety, PressioError, PressioOption, PressioOptions,
+};
+
+/// An error bound that a compressor should respect.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ErrorBound {
+    /// Absolute error bound, set using `pressio:abs`.
+    Absolute(f64),
+    /// Error bound relative to the value range of the data, set using
+    /// `pressio:rel`.
+    ValueRangeRelative(f64),
+    /// Error bound relative to each value, set using `pressio:pw_rel`.
+    PointwiseRelative(f64),
+    /// Lossless compression, set using `pressio:lossless`.
+    Lossless,
+    /// Fixed number of bits per value, set using the compressor-specific
+    /// `<prefix>:rate` option, e.g. `zfp:rate`.
+    FixedRate(f64),
+    /// Fixed number of bit planes, set using the compressor-specific
+    /// `<prefix>:precision` option, e.g. `zfp:precision`.
+    FixedPrecision(u32),
+}
+
+impl ErrorBound {
+    /// The option key that configures this error bound for the compressor
+    /// with the given `prefix`.
+    pub fn option_key(&self, prefix: &str) -> String {
+        match self {
+            Self::Absolute(_) => String::from("pressio:abs"),
+            Self::ValueRangeRelative(_) => String::from("pressio:rel"),
+            Self::PointwiseRelative(_) => String::from("pressio:pw_rel"),
+            Self::Lossless => String::from("pressio:lossless"),
+            Self::FixedRate(_) => format!("{prefix}:rate"),
+            Self::FixedPrecision(_) => format!("{prefix}:precision"),
+        }
+    }
+
+    /// The numeric value of the bound, or [`None`] for lossless compression.
+    pub fn value(&self) -> Option<f64> {
+        match *self {
+            Self::Absolute(bound)
+            | Self::ValueRangeRelative(bound)
+            | Self::PointwiseRelative(bound)
+            | Self::FixedRate(bound) => Some(bound),
+            Self::FixedPrecision(precision) => Some(f64::from(precision)),
+            Self::Lossless => None,
+        }
+    }
+
+    const fn mode_name(&self) -> &'static str {
+        match self {
+            Self::Absolute(_) => "absolute",
+            Self::ValueRangeRelative(_) => "value-range relative",
+            Self::PointwiseRelative(_) => "pointwise relative",
+            Self::Lossless => "lossless",
+            Self::FixedRate(_) => "fixed-rate",
+            Self::FixedPrecision(_) => "fixed-precision",
+        }
+    }
+
+    fn into_option(self) -> PressioOption {
+        match self {
+            Self::Absolute(bound)
+            | Self::ValueRangeRelative(bound)
+            | Self::PointwiseRelative(bound)
+            | Self::FixedRate(bound) => PressioOption::float64(Some(bound)),
+            Self::FixedPrecision(precision) => PressioOption::uint32(Some(precision)),
+            // libpressio interprets the value as the lossless compression level
+            Self::Lossless => PressioOption::int32(Some(1)),
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorBound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Absolute(bound) => write!(f, "abs={bound}"),
+            Self::ValueRangeRelative(bound) => write!(f, "rel={bound}"),
+            Self::PointwiseRelative(bound) => write!(f, "pw_rel={bound}"),
+            Self::Lossless => f.write_str("lossless"),
+            Self::FixedRate(rate) => write!(f, "rate={rate}"),
+            Self::FixedPrecision(precision) => write!(f, "precision={precision}"),
+        }
+    }
+}
+
+impl PressioCompressor {
+    /// Configure the compressor to respect the error `bound`.
+    ///
+    /// An error is returned if the compressor does not expose the option for
+    /// the error bound mode (see [`ErrorBound::option_key`]) in its
+    /// [`PressioCompressor::get_options`].
+    pub fn set_error_bound(&mut self, bound: ErrorBound) -> Result<(), PressioError> {
+        let prefix = String::from(self.get_prefix()?);
+        let key = bound.option_key(&prefix);
+
+        let Some(option_type) = self.get_options()?.get(&key)? else {
+            return Err(PressioError {
+                error_code: 1,
+                message: format!(
+                    "compressor `{prefix}` does not support {} error bounds since it has no `{key}` option",
+                    bound.mode_name(),
+                ),
+            });
+        };
+
+        let mut options = PressioOptions::new()?;
+        options.set(&key, option_type.copy_type_only())?;
+        options
+            .set_with_cast(&key, bound.into_option(), PressioConversionSafety::Explicit)
+            .map_err(|err| PressioError {
+                error_code: err.error_code,
+                message: format!(
+                    "failed to convert the {} error bound `{bound}` to the {} type of option `{key}`",
+                    bound.mode_name(),
+                    option_type.type_name(),
+                ),
+            })?;
+
+        self.set_options(&options)
+    }
+
+    /// Check whether the compressor exposes the option for the error bound
+    /// mode of `bound` in its [`PressioCompressor::get_options`].
+    pub fn supports_error_bound(&self, bound: ErrorBound) -> Result<bool, PressioError> {
+        self.get_options()?
+            .has_option(bound.option_key(self.get_prefix()?))
+    }
+}
@@ -0,0 +1,272 @@
+use ndarray::ArrayViewD;
+
+use crate::{NumericVisitor, PressioData, PressioDtype, PressioError, PressioNumeric};
+
+/// The number of equal-width bins of [`ErrorStats::histogram`].
+const NUM_HISTOGRAM_BINS: usize = 32;
+
+/// The largest lag of [`ErrorStats::autocorrelation`].
+const MAX_AUTOCORRELATION_LAG: usize = 10;
+
+/// The pointwise error of data against a reference, with summary statistics
+/// like those of the libpressio `error_stat` metric, see
+/// [`PressioData::error_against`].
+#[derive(Clone, Debug)]
+pub struct ErrorStats {
+    /// The pointwise difference `data - reference` as
+    /// [`PressioDtype::F64`] data with the shape of the reference.
+    pub difference: PressioData,
+    /// The summary statistics of the finite differences.
+    pub summary: ErrorSummary,
+    /// The histogram of the finite differences.
+    pub histogram: ErrorHistogram,
+    /// The autocorrelation of the finite differences in memory order, where
+    /// the `i`-th entry is for lag `i + 1`.
+    ///
+    /// An entry is zero if the differences are constant.
+    pub autocorrelation: Vec<f64>,
+}
+
+/// Summary statistics of the pointwise error, see [`ErrorStats`].
+///
+/// The statistics only consider finite differences, and are NaN if there are
+/// none.
+///
+/// If all finite differences are zero, the PSNR is infinite and the maximum
+/// relative error is zero, even if the reference is constant. Otherwise, a
+/// constant reference, whose value range is zero, has an infinite maximum
+/// relative error and a PSNR of negative infinity.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ErrorSummary {
+    pub num_elements: usize,
+    /// The number of elements whose difference is NaN or infinite, e.g.
+    /// because the reference is NaN.
+    pub num_non_finite: usize,
+    pub min_error: f64,
+    pub max_error: f64,
+    /// The mean error, i.e. the bias of the data.
+    pub mean_error: f64,
+    pub max_abs_error: f64,
+    /// The maximum absolute error relative to the value range of the
+    /// reference.
+    pub max_rel_error: f64,
+    /// The value range of the finite reference values.
+    pub value_range: f64,
+    pub mse: f64,
+    pub rmse: f64,
+    /// The peak signal-to-noise ratio in dB, relative to the value range of
+    /// the reference.
+    pub psnr: f64,
+}
+
+/// A histogram of the pointwise error with equal-width bins between the
+/// minimum and maximum error, see [`ErrorStats`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ErrorHistogram {
+    pub min: f64,
+    pub max: f64,
+    /// The number of finite differences in each bin, where the last bin also
+    /// includes the maximum error.
+    pub counts: Vec<usize>,
+}
+
+impl ErrorHistogram {
+    pub fn bin_width(&self) -> f64 {
+        (self.max - self.min) / self.counts.len() as f64
+    }
+}
+
+impl PressioData {
+    /// Compute the pointwise error of the data, e.g. decompressed data,
+    /// against the `reference`, e.g. the original data, with its summary
+    /// statistics, histogram, and autocorrelation.
+    ///
+    /// The data and the reference may have different numeric dtypes, whose
+    /// values are converted to [`f64`]. An error is returned if either has
+    /// no numeric values or if their shapes differ.
+    pub fn error_against(&self, reference: &Self) -> Result<ErrorStats, PressioError> {
+        let shape = reference.shape();
+        if self.shape() != shape {
+            return Err(error_stat_error(&format!(
+                "data with shape {:?} cannot be compared against a reference with shape {shape:?}",
+                self.shape()
+            )));
+        }
+
+        let (Some(values), Some(reference_values)) = (
+            self.map_numeric(ValuesVisitor),
+            reference.map_numeric(ValuesVisitor),
+        ) else {
+            return Err(error_stat_error(
+                "cannot compute the error of data without numeric values",
+            ));
+        };
+
+        let difference = values
+            .iter()
+            .zip(&reference_values)
+            .map(|(x, y)| x - y)
+            .collect::<Vec<_>>();
+
+        let bytes = difference
+            .iter()
+            .flat_map(|x| x.to_ne_bytes())
+            .collect::<Vec<_>>();
+
+        Ok(ErrorStats {
+            difference: Self::from_bytes(PressioDtype::F64, &shape, &bytes),
+            summary: summary(&difference, &reference_values),
+            histogram: histogram(&difference),
+            autocorrelation: autocorrelation(&difference),
+        })
+    }
+}
+
+fn summary(difference: &[f64], reference: &[f64]) -> ErrorSummary {
+    let (min_value, max_value) = reference
+        .iter()
+        .filter(|x| x.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &x| {
+            (min.min(x), max.max(x))
+        });
+    let value_range = if min_value <= max_value {
+        max_value - min_value
+    } else {
+        f64::NAN
+    };
+
+    let mut min_error = f64::INFINITY;
+    let mut max_error = f64::NEG_INFINITY;
+    let mut max_abs_error = 0.0_f64;
+    let mut sum = 0.0;
+    let mut sum_squares = 0.0;
+    let mut num_finite = 0_usize;
+
+    for &e in difference.iter().filter(|e| e.is_finite()) {
+        min_error = min_error.min(e);
+        max_error = max_error.max(e);
+        max_abs_error = max_abs_error.max(e.abs());
+        sum += e;
+        sum_squares += e * e;
+        num_finite += 1;
+    }
+
+    let num_elements = difference.len();
+    let num_non_finite = num_elements - num_finite;
+
+    if num_finite == 0 {
+        return ErrorSummary {
+            num_elements,
+            num_non_finite,
+            min_error: f64::NAN,
+            max_error: f64::NAN,
+            mean_error: f64::NAN,
+            max_abs_error: f64::NAN,
+            max_rel_error: f64::NAN,
+            value_range,
+            mse: f64::NAN,
+            rmse: f64::NAN,
+            psnr: f64::NAN,
+        };
+    }
+
+    let mse = sum_squares / num_finite as f64;
+
+    // avoid NaN from 0/0 and inf-inf for exact data or a constant reference
+    let (max_rel_error, psnr) = if mse == 0.0 {
+        (0.0, f64::INFINITY)
+    } else if value_range == 0.0 {
+        (f64::INFINITY, f64::NEG_INFINITY)
+    } else {
+        (
+            max_abs_error / value_range,
+            20.0 * value_range.log10() - 10.0 * mse.log10(),
+        )
+    };
+
+    ErrorSummary {
+        num_elements,
+        num_non_finite,
+        min_error,
+        max_error,
+        mean_error: sum / num_finite as f64,
+        max_abs_error,
+        max_rel_error,
+        value_range,
+        mse,
+        rmse: mse.sqrt(),
+        psnr,
+    }
+}
+
+fn histogram(difference: &[f64]) -> ErrorHistogram {
+    let (min, max) = difference
+        .iter()
+        .filter(|e| e.is_finite())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &e| {
+            (min.min(e), max.max(e))
+        });
+
+    let mut counts = vec![0; NUM_HISTOGRAM_BINS];
+
+    if min > max {
+        return ErrorHistogram {
+            min: f64::NAN,
+            max: f64::NAN,
+            counts,
+        };
+    }
+
+    let width = (max - min) / NUM_HISTOGRAM_BINS as f64;
+    for &e in difference.iter().filter(|e| e.is_finite()) {
+        // constant errors all fall into the first bin
+        let bin = if width > 0.0 {
+            (((e - min) / width) as usize).min(NUM_HISTOGRAM_BINS - 1)
+        } else {
+            0
+        };
+        counts[bin] += 1;
+    }
+
+    ErrorHistogram { min, max, counts }
+}
+
+fn autocorrelation(difference: &[f64]) -> Vec<f64> {
+    let finite = difference.iter().filter(|e| e.is_finite());
+    let num_finite = finite.clone().count();
+    let mean = finite.clone().sum::<f64>() / num_finite as f64;
+    let variance = finite.map(|e| (e - mean).powi(2)).sum::<f64>();
+
+    (1..=MAX_AUTOCORRELATION_LAG.min(difference.len().saturating_sub(1)))
+        .map(|lag| {
+            if variance == 0.0 {
+                return 0.0;
+            }
+            let covariance = difference
+                .iter()
+                .zip(&difference[lag..])
+                .filter(|(a, b)| a.is_finite() && b.is_finite())
+                .map(|(a, b)| (a - mean) * (b - mean))
+                .sum::<f64>();
+            covariance / variance
+        })
+        .collect()
+}
+
+/// Collects the values of the data as [`f64`], in memory order.
+struct ValuesVisitor;
+
+impl NumericVisitor for ValuesVisitor {
+    type Output = Vec<f64>;
+
+    fn visit<T: PressioNumeric>(self, array: ArrayViewD<'_, T>) -> Self::Output {
+        array.iter().map(|&x| x.to_f64()).collect()
+    }
+}
+
+fn error_stat_error(message: &str) -> PressioError {
+    PressioError {
+        error_code: 1,
+        message: String::from(message),
+    }
+}
@@ -0,0 +1,165 @@
+use std::{borrow::Borrow, ops::Deref};
+
+use half::{bf16, f16};
+use ndarray::{Array, ArrayBase, Data, Dimension, IxDyn};
+
+use crate::{PressioArray, PressioData};
+
+/// A half-precision floating-point format, which libpressio does not support
+/// natively.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HalfPrecision {
+    /// IEEE 754 binary16, see [`half::f16`].
+    F16,
+    /// bfloat16, see [`half::bf16`].
+    BF16,
+}
+
+impl HalfPrecision {
+    /// Narrow floating-point data, e.g. decompressed data, back into an
+    /// array of this precision, rounding to the nearest representable value.
+    ///
+    /// Returns [`None`] if the data has no values or is not floating-point.
+    pub fn narrow(self, data: &PressioData) -> Option<HalfArray> {
+        match self {
+            Self::F16 => data.clone_into_half_array().map(HalfArray::F16),
+            Self::BF16 => data.clone_into_half_array().map(HalfArray::BF16),
+        }
+    }
+}
+
+impl std::fmt::Display for HalfPrecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::F16 => "float16",
+            Self::BF16 => "bfloat16",
+        })
+    }
+}
+
+/// An array of half-precision values.
+#[derive(Debug, PartialEq, Clone)]
+pub enum HalfArray {
+    F16(Array<f16, IxDyn>),
+    BF16(Array<bf16, IxDyn>),
+}
+
+impl HalfArray {
+    pub const fn precision(&self) -> HalfPrecision {
+        match self {
+            Self::F16(_) => HalfPrecision::F16,
+            Self::BF16(_) => HalfPrecision::BF16,
+        }
+    }
+}
+
+pub trait HalfElement: sealed::HalfElement {
+    const PRECISION: HalfPrecision;
+}
+
+mod sealed {
+    pub trait HalfElement: Copy {
+        fn to_f32(self) -> f32;
+        fn from_f32(x: f32) -> Self;
+        fn from_f64(x: f64) -> Self;
+    }
+}
+
+macro_rules! impl_half_element {
+    ($($variant:ident($ty:ty)),*) => {
+        $(
+            impl sealed::HalfElement for $ty {
+                fn to_f32(self) -> f32 {
+                    <$ty>::to_f32(self)
+                }
+
+                fn from_f32(x: f32) -> Self {
+                    <$ty>::from_f32(x)
+                }
+
+                fn from_f64(x: f64) -> Self {
+                    <$ty>::from_f64(x)
+                }
+            }
+
+            impl HalfElement for $ty {
+                const PRECISION: HalfPrecision = HalfPrecision::$variant;
+            }
+        )*
+    };
+}
+
+impl_half_element! {
+    F16(f16),
+    BF16(bf16)
+}
+
+/// [`PressioDtype::F32`](crate::PressioDtype::F32) data that was widened
+/// from a half-precision array and remembers its original precision.
+///
+/// The data dereferences to a [`PressioData`], which can e.g. be compressed
+/// directly.
+pub struct HalfPressioData {
+    data: PressioData,
+    precision: HalfPrecision,
+}
+
+impl HalfPressioData {
+    /// The precision of the array that the data was widened from.
+    pub const fn precision(&self) -> HalfPrecision {
+        self.precision
+    }
+
+    /// Narrow the data back into an array of its original precision.
+    ///
+    /// Use [`HalfPrecision::narrow`] to restore decompressed data.
+    pub fn clone_into_original_array(&self) -> HalfArray {
+        match self.precision.narrow(&self.data) {
+            Some(array) => array,
+            None => unreachable!("widened data always has float values"),
+        }
+    }
+
+    pub fn into_inner(self) -> PressioData {
+        self.data
+    }
+}
+
+impl Deref for HalfPressioData {
+    type Target = PressioData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl PressioData {
+    /// Copy a half-precision array into new
+    /// [`PressioDtype::F32`](crate::PressioDtype::F32) data, widening each
+    /// value losslessly.
+    ///
+    /// The returned data remembers the original precision, so that it can
+    /// be restored with [`HalfPressioData::clone_into_original_array`] or
+    /// [`HalfPrecision::narrow`].
+    pub fn new_copied_half<T: HalfElement, S: Data<Elem = T>, D: Dimension>(
+        x: impl Borrow<ArrayBase<S, D>>,
+    ) -> HalfPressioData {
+        let widened = x.borrow().map(|&x| x.to_f32());
+        HalfPressioData {
+            data: Self::new_copied(widened),
+            precision: T::PRECISION,
+        }
+    }
+
+    /// Copy floating-point data into a half-precision array, rounding each
+    /// value to the nearest representable value.
+    ///
+    /// Returns [`None`] if the data has no values or is not floating-point.
+    pub fn clone_into_half_array<T: HalfElement>(&self) -> Option<Array<T, IxDyn>> {
+        match self.clone_into_array()? {
+            PressioArray::F32(array) => Some(array.mapv(T::from_f32)),
+            PressioArray::F64(array) => Some(array.mapv(T::from_f64)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::{
+    ffi::{CStr, CString},
+    marker::PhantomData,
+    ptr::NonNull,
+    rc::Rc,
+};
+
+use crate::{Pressio, PressioData, PressioError, PressioOptions};
+
+impl Pressio {
+    /// Instantiate the io module with the given `id`, e.g. `posix` or `numpy`.
+    ///
+    /// See [`supported_io_modules`](crate::supported_io_modules) for the
+    /// available io modules.
+    pub fn get_io<S: AsRef<str>>(&mut self, id: S) -> Result<PressioIo, PressioError> {
+        let id = id.as_ref();
+        let id_cstr =
+            CString::new(id).map_err(|err| PressioError::null_error(err, "io module id"))?;
+        let ptr =
+            unsafe { libpressio_sys::pressio_get_io(self.library.as_ptr(), id_cstr.as_ptr()) };
+        let Some(ptr) = NonNull::new(ptr) else {
+            return Err(self.get_error());
+        };
+        Ok(PressioIo {
+            ptr,
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// An io module that reads and writes [`PressioData`] from and to persistent
+/// storage, e.g. files.
+pub struct PressioIo {
+    // pressio_io is conservatively !Send and !Sync
+    // - impl !Send from PhantomData<Rc>
+    // - impl !Sync from PhantomData<Rc>
+    ptr: NonNull<libpressio_sys::pressio_io>,
+    _marker: PhantomData<Rc<()>>,
+}
+
+impl PressioIo {
+    /// Read data using the io module.
+    ///
+    /// The optional `template` describes the dtype and shape of the data to
+    /// read, which is required by io modules that read raw bytes, such as
+    /// `posix`. Without a template, such modules read the data as bytes.
+    pub fn read(&mut self, template: Option<PressioData>) -> Result<PressioData, PressioError> {
+        // libpressio takes ownership of the template and either returns it or
+        //  frees it
+        let template = template.map_or(std::ptr::null_mut(), |template| {
+            let ptr = template.data.as_ptr();
+            std::mem::forget(template);
+            ptr
+        });
+        let data = unsafe { libpressio_sys::pressio_io_read(self.ptr.as_ptr(), template) };
+        match NonNull::new(data) {
+            Some(data) => Ok(PressioData { data }),
+            None => Err(self.get_error()),
+        }
+    }
+
+    /// Write the `data` using the io module.
+    pub fn write(&mut self, data: &PressioData) -> Result<(), PressioError> {
+        let rc = unsafe { libpressio_sys::pressio_io_write(self.ptr.as_ptr(), data.as_raw()) };
+        if rc == 0 {
+            Ok(())
+        } else {
+            Err(self.get_error())
+        }
+    }
+
+    pub fn set_options(&mut self, options: &PressioOptions) -> Result<(), PressioError> {
+        let rc = unsafe {
+            libpressio_sys::pressio_io_set_options(
+                self.ptr.as_ptr(),
+                options.ptr.as_ptr().cast_const(),
+            )
+        };
+        if rc == 0 {
+            Ok(())
+        } else {
+            Err(self.get_error())
+        }
+    }
+
+    pub fn get_options(&self) -> Result<PressioOptions, PressioError> {
+        let options = unsafe { libpressio_sys::pressio_io_get_options(self.ptr.as_ptr()) };
+        match NonNull::new(options) {
+            Some(ptr) => Ok(PressioOptions { ptr }),
+            None => Err(self.get_error()),
+        }
+    }
+
+    pub fn get_configuration(&self) -> Result<PressioOptions, PressioError> {
+        let config = unsafe { libpressio_sys::pressio_io_get_configuration(self.ptr.as_ptr()) };
+        match NonNull::new(config) {
+            Some(ptr) => Ok(PressioOptions { ptr }),
+            None => Err(self.get_error()),
+        }
+    }
+
+    pub fn get_documentation(&self) -> Result<PressioOptions, PressioError> {
+        let docs = unsafe { libpressio_sys::pressio_io_get_documentation(self.ptr.as_ptr()) };
+        match NonNull::new(docs) {
+            Some(ptr) => Ok(PressioOptions { ptr }),
+            None => Err(self.get_error()),
+        }
+    }
+
+    pub fn get_prefix(&self) -> Result<&str, PressioError> {
+        let prefix_ptr = unsafe { libpressio_sys::pressio_io_get_prefix(self.ptr.as_ptr()) };
+        let prefix = unsafe { CStr::from_ptr(prefix_ptr) };
+        prefix
+            .to_str()
+            .map_err(|err| PressioError::utf8_error(err, "io module id"))
+    }
+
+    fn get_error(&self) -> PressioError {
+        let error_code = unsafe { libpressio_sys::pressio_io_error_code(self.ptr.as_ptr()) };
+        let message = unsafe {
+            let message = libpressio_sys::pressio_io_error_msg(self.ptr.as_ptr());
+            CStr::from_ptr(message).to_str()
+        };
+        match message {
+            Ok(message) => PressioError {
+                error_code,
+                message: String::from(message),
+            },
+            Err(err) => PressioError::utf8_error(err, "io error message"),
+        }
+    }
+}
+
+impl Drop for PressioIo {
+    fn drop(&mut self) {
+        unsafe {
+            libpressio_sys::pressio_io_free(self.ptr.as_ptr());
+        }
+    }
+}
@@ -4,7 +4,7 @@
 
 use std::{
     borrow::Borrow,
-    ffi::{CStr, CString, c_char, c_int, c_uchar, c_void},
+    ffi::{CStr, CString, IntoStringError, c_char, c_int, c_uchar, c_void},
     iter::FusedIterator,
     marker::PhantomData,
     ops::{Deref, DerefMut},
@@ -21,6 +21,58 @@ use libpressio_sys::{
 use ndarray::{Array, ArrayBase, ArrayView, CowArray, Data, Dimension, IxDyn};
 use thiserror::Error;
 
+#[cfg(feature = "arrow")]
+mod arrow;
+mod assign;
+mod bench;
+mod cast;
+#[cfg(feature = "complex")]
+mod complex;
+mod diff;
+mod error_bound;
+mod error_stat;
+#[cfg(feature = "half")]
+mod half_precision;
+mod io;
+mod masked;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod namespace;
+mod npy;
+mod schema;
+mod search;
+mod select;
+mod stats;
+mod verify;
+mod visit;
+
+#[cfg(feature = "arrow")]
+pub use arrow::ArrowPressioData;
+pub use bench::{Bench, BenchCase, BenchFailure, BenchRecord, BenchReport, OptionGrid};
+pub use cast::{CastOverflow, CastPolicy, CastReport, CastRounding};
+#[cfg(feature = "complex")]
+pub use complex::ComplexElement;
+pub use diff::{OptionChange, OptionsDiff};
+pub use error_bound::ErrorBound;
+pub use error_stat::{ErrorHistogram, ErrorStats, ErrorSummary};
+#[cfg(feature = "half")]
+pub use half_precision::{HalfArray, HalfElement, HalfPrecision, HalfPressioData};
+pub use io::PressioIo;
+pub use masked::{MaskPolicy, MaskedCompressed};
+#[cfg(feature = "mmap")]
+pub use mmap::MappedPressioData;
+pub use namespace::PressioOptionsTree;
+#[cfg(feature = "npz")]
+pub use npy::{read_npz, write_npz};
+pub use schema::{CompressorSchema, PressioOptionKind, PressioSchemaEntry};
+pub use search::{
+    QualityMetric, QualitySearch, QualitySearchResult, QualitySearchStep, RatioSearch,
+    RatioSearchResult, RatioSearchStep, SearchMode,
+};
+pub use stats::DataStats;
+pub use verify::{RoundtripReport, RoundtripViolation, verify_roundtrip};
+pub use visit::{NumericVisitor, PressioNumeric};
+
 static REGISTRATION: LazyLock<()> = LazyLock::new(|| unsafe {
     libpressio_sys::pressio_register_all();
 });
@@ -53,6 +105,13 @@ impl PressioError {
             message: format!("invalid null byte in {context}"),
         }
     }
+
+    fn io_error(err: &std::io::Error, context: &str) -> Self {
+        PressioError {
+            error_code: 1,
+            message: format!("failed to {context}: {err}"),
+        }
+    }
 }
 
 pub fn major_version() -> u32 {
@@ -321,6 +380,10 @@ impl PressioCompressor {
         }
     }
 
+    pub fn get_schema(&self) -> Result<CompressorSchema, PressioError> {
+        CompressorSchema::from_compressor(self)
+    }
+
     pub fn get_metrics_options(&self) -> Result<PressioOptions, PressioError> {
         let options =
             unsafe { libpressio_sys::pressio_compressor_metrics_get_options(self.as_raw()) };
@@ -493,6 +556,16 @@ impl PressioDtype {
         unsafe { libpressio_sys::pressio_dtype_is_signed(self.into_raw()) != 0 }
     }
 
+    /// The size of a single element of this dtype in bytes.
+    pub const fn size_in_bytes(self) -> usize {
+        match self {
+            Self::Byte | Self::Bool | Self::U8 | Self::I8 => 1,
+            Self::U16 | Self::I16 => 2,
+            Self::U32 | Self::I32 | Self::F32 => 4,
+            Self::U64 | Self::I64 | Self::F64 => 8,
+        }
+    }
+
     fn from_raw(dtype: libpressio_sys::pressio_dtype) -> Option<Self> {
         match dtype {
             libpressio_sys::pressio_dtype_pressio_byte_dtype => Some(PressioDtype::Byte),
@@ -590,14 +663,38 @@ pub trait PressioElement: sealed::PressioElement {
 mod sealed {
     pub trait PressioElement: Copy {
         const DTYPE: libpressio_sys::pressio_dtype;
+
+        fn as_array(array: &super::PressioArray) -> Option<&super::Array<Self, super::IxDyn>>;
+
+        fn into_array(
+            array: super::PressioArray,
+        ) -> Result<super::Array<Self, super::IxDyn>, super::PressioArray>;
     }
 }
 
 macro_rules! impl_pressio_element {
-    ($($variant:ident($ty:ty) => $impl:ident),*) => {
+    ($($variant:ident $(| $alias:ident)?($ty:ty) => $impl:ident),*) => {
         $(
             impl sealed::PressioElement for $ty {
                 const DTYPE: libpressio_sys::pressio_dtype = libpressio_sys::$impl;
+
+                fn as_array(array: &PressioArray) -> Option<&Array<Self, IxDyn>> {
+                    match array {
+                        PressioArray::$variant(array) $(| PressioArray::$alias(array))? => {
+                            Some(array)
+                        }
+                        _ => None,
+                    }
+                }
+
+                fn into_array(array: PressioArray) -> Result<Array<Self, IxDyn>, PressioArray> {
+                    match array {
+                        PressioArray::$variant(array) $(| PressioArray::$alias(array))? => {
+                            Ok(array)
+                        }
+                        array => Err(array),
+                    }
+                }
             }
 
             impl PressioElement for $ty {
@@ -609,7 +706,8 @@ macro_rules! impl_pressio_element {
 
 impl_pressio_element! {
     Bool(bool) => pressio_dtype_pressio_bool_dtype,
-    U8(u8) => pressio_dtype_pressio_uint8_dtype,
+    // byte arrays also have u8 elements
+    U8 | Byte(u8) => pressio_dtype_pressio_uint8_dtype,
     U16(u16) => pressio_dtype_pressio_uint16_dtype,
     U32(u32) => pressio_dtype_pressio_uint32_dtype,
     U64(u64) => pressio_dtype_pressio_uint64_dtype,
@@ -621,6 +719,61 @@ impl_pressio_element! {
     F64(f64) => pressio_dtype_pressio_double_dtype
 }
 
+/// The order in which the dimensions of an ndarray are passed to libpressio.
+///
+/// ndarray arrays in standard layout are stored in C order, i.e. their last
+/// axis varies fastest in memory. libpressio and many of its compressors
+/// instead treat the first dimension as the fastest-varying one. The layout
+/// never changes the order of the elements in memory, only the order of the
+/// dimensions that libpressio sees.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PressioLayout {
+    /// The dimensions are passed through in the same order as the ndarray
+    /// shape, so that libpressio's first dimension is the slowest-varying.
+    #[default]
+    C,
+    /// The dimensions are reversed, so that libpressio's first dimension is
+    /// the fastest-varying, as libpressio expects.
+    Fortran,
+}
+
+impl PressioLayout {
+    /// Convert an ndarray `shape` into the libpressio dimensions, or the
+    /// libpressio dimensions back into an ndarray shape.
+    pub fn convert_dims(self, dims: &[usize]) -> Vec<usize> {
+        match self {
+            Self::C => dims.to_vec(),
+            Self::Fortran => dims.iter().rev().copied().collect(),
+        }
+    }
+}
+
+/// How an array was passed to libpressio by
+/// [`PressioData::new_with_shared_or_copied`] or
+/// [`PressioData::new_with_bytes_shared_or_copied`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SharedOrCopied {
+    /// The array is in standard layout and was shared without copying.
+    Shared,
+    /// The array is Fortran-contiguous and was shared without copying, but
+    /// with its dimensions reversed.
+    SharedReversed,
+    /// The array was copied into a temporary buffer in standard layout.
+    Copied,
+}
+
+impl SharedOrCopied {
+    fn of<S: Data, D: Dimension>(x: &ArrayBase<S, D>, share_fortran: bool) -> Self {
+        if x.is_standard_layout() {
+            Self::Shared
+        } else if share_fortran && x.t().is_standard_layout() {
+            Self::SharedReversed
+        } else {
+            Self::Copied
+        }
+    }
+}
+
 pub struct PressioData {
     // pressio_data is Send but !Sync
     // - impl Send below
@@ -632,7 +785,17 @@ unsafe impl Send for PressioData {}
 
 impl PressioData {
     pub fn new_empty<D: AsRef<[usize]>>(dtype: PressioDtype, shape: D) -> PressioData {
-        let shape = shape.as_ref();
+        Self::new_empty_with_layout(dtype, shape, PressioLayout::C)
+    }
+
+    /// Create new data without values, whose ndarray `shape` is converted
+    /// into libpressio dimensions using the `layout`.
+    pub fn new_empty_with_layout<D: AsRef<[usize]>>(
+        dtype: PressioDtype,
+        shape: D,
+        layout: PressioLayout,
+    ) -> PressioData {
+        let shape = layout.convert_dims(shape.as_ref());
         let data = unsafe {
             libpressio_sys::pressio_data_new_empty(dtype.into_raw(), shape.len(), shape.as_ptr())
         };
@@ -643,99 +806,283 @@ impl PressioData {
     pub fn new_copied<T: PressioElement, S: Data<Elem = T>, D: Dimension>(
         x: impl Borrow<ArrayBase<S, D>>,
     ) -> Self {
-        Self::new_copied_inner(x.borrow(), <T as sealed::PressioElement>::DTYPE)
+        Self::new_copied_with_layout(x, PressioLayout::C)
+    }
+
+    /// Copy the array into new data, whose dimensions are ordered according
+    /// to the `layout`.
+    pub fn new_copied_with_layout<T: PressioElement, S: Data<Elem = T>, D: Dimension>(
+        x: impl Borrow<ArrayBase<S, D>>,
+        layout: PressioLayout,
+    ) -> Self {
+        Self::new_copied_inner(x.borrow(), <T as sealed::PressioElement>::DTYPE, layout)
     }
 
     pub fn new_bytes_copied<S: Data<Elem = c_uchar>, D: Dimension>(
         x: impl Borrow<ArrayBase<S, D>>,
     ) -> Self {
-        Self::new_copied_inner(x.borrow(), libpressio_sys::pressio_dtype_pressio_byte_dtype)
+        Self::new_bytes_copied_with_layout(x, PressioLayout::C)
+    }
+
+    /// Copy the byte array into new data, whose dimensions are ordered
+    /// according to the `layout`.
+    pub fn new_bytes_copied_with_layout<S: Data<Elem = c_uchar>, D: Dimension>(
+        x: impl Borrow<ArrayBase<S, D>>,
+        layout: PressioLayout,
+    ) -> Self {
+        Self::new_copied_inner(
+            x.borrow(),
+            libpressio_sys::pressio_dtype_pressio_byte_dtype,
+            layout,
+        )
     }
 
     fn new_copied_inner<T: Copy, S: Data<Elem = T>, D: Dimension>(
         x: &ArrayBase<S, D>,
         dtype: libpressio_sys::pressio_dtype,
+        layout: PressioLayout,
     ) -> Self {
-        let shape = x.shape().to_vec();
+        let shape = layout.convert_dims(x.shape());
 
-        let data = if x.is_standard_layout() {
-            unsafe {
+        if x.is_standard_layout() {
+            let data = unsafe {
                 libpressio_sys::pressio_data_new_copy(
                     dtype,
                     x.as_ptr().cast(),
                     shape.len(),
                     shape.as_ptr(),
                 )
-            }
-        } else {
-            let x_vec: Vec<T> = x.iter().copied().collect();
-            unsafe {
-                libpressio_sys::pressio_data_new_copy(
-                    dtype,
-                    x_vec.as_ptr().cast(),
-                    shape.len(),
-                    shape.as_ptr(),
-                )
-            }
-        };
-        let data = NonNull::new(data).expect("pressio_data_new_copy must not return null");
-        PressioData { data }
+            };
+            let data = NonNull::new(data).expect("pressio_data_new_copy must not return null");
+            return PressioData { data };
+        }
+
+        // copy the elements straight into the libpressio buffer, instead of
+        // first collecting them into a temporary vector
+        let data =
+            unsafe { libpressio_sys::pressio_data_new_owning(dtype, shape.len(), shape.as_ptr()) };
+        let data = NonNull::new(data).expect("pressio_data_new_owning must not return null");
+        let data = PressioData { data };
+
+        let mut num_bytes = 0;
+        let ptr = unsafe { libpressio_sys::pressio_data_ptr(data.as_raw(), &raw mut num_bytes) }
+            .cast::<T>();
+        debug_assert_eq!(num_bytes, std::mem::size_of::<T>() * x.len());
+        for (i, x) in x.iter().enumerate() {
+            // SAFETY: the new data owns a buffer with space for x.len()
+            //         elements, which may not be aligned
+            unsafe { ptr.add(i).write_unaligned(*x) };
+        }
+
+        data
     }
 
     pub fn new_with_shared<T: PressioElement, S: Data<Elem = T>, D: Dimension, O>(
         x: impl Borrow<ArrayBase<S, D>>,
         with: impl for<'a> FnOnce(&'a Self) -> O,
     ) -> O {
-        Self::new_with_shared_inner(x.borrow(), <T as sealed::PressioElement>::DTYPE, with)
+        Self::new_with_shared_with_layout(x, PressioLayout::C, with)
+    }
+
+    /// Share the array with libpressio as data, whose dimensions are ordered
+    /// according to the `layout`, while `with` is called.
+    pub fn new_with_shared_with_layout<T: PressioElement, S: Data<Elem = T>, D: Dimension, O>(
+        x: impl Borrow<ArrayBase<S, D>>,
+        layout: PressioLayout,
+        with: impl for<'a> FnOnce(&'a Self) -> O,
+    ) -> O {
+        let x = x.borrow();
+        Self::new_with_shared_inner(
+            x,
+            <T as sealed::PressioElement>::DTYPE,
+            layout,
+            SharedOrCopied::of(x, false),
+            with,
+        )
     }
 
     pub fn new_with_bytes_shared<S: Data<Elem = c_uchar>, D: Dimension, O>(
         x: impl Borrow<ArrayBase<S, D>>,
         with: impl for<'a> FnOnce(&'a Self) -> O,
     ) -> O {
+        Self::new_with_bytes_shared_with_layout(x, PressioLayout::C, with)
+    }
+
+    /// Share the byte array with libpressio as data, whose dimensions are
+    /// ordered according to the `layout`, while `with` is called.
+    pub fn new_with_bytes_shared_with_layout<S: Data<Elem = c_uchar>, D: Dimension, O>(
+        x: impl Borrow<ArrayBase<S, D>>,
+        layout: PressioLayout,
+        with: impl for<'a> FnOnce(&'a Self) -> O,
+    ) -> O {
+        let x = x.borrow();
         Self::new_with_shared_inner(
+            x,
+            libpressio_sys::pressio_dtype_pressio_byte_dtype,
+            layout,
+            SharedOrCopied::of(x, false),
+            with,
+        )
+    }
+
+    /// Share the array with libpressio as data, whose dimensions are ordered
+    /// according to the `layout`, while `with` is called, and report whether
+    /// the array was shared or copied.
+    ///
+    /// Unlike [`PressioData::new_with_shared_with_layout`], arrays that are
+    /// Fortran-contiguous are shared without copying, but with their
+    /// dimensions reversed, i.e. libpressio sees the transposed array. All
+    /// other arrays that are not in standard layout are copied into a
+    /// temporary buffer.
+    pub fn new_with_shared_or_copied<T: PressioElement, S: Data<Elem = T>, D: Dimension, O>(
+        x: impl Borrow<ArrayBase<S, D>>,
+        layout: PressioLayout,
+        with: impl for<'a> FnOnce(&'a Self) -> O,
+    ) -> (O, SharedOrCopied) {
+        Self::new_with_shared_or_copied_inner(
+            x.borrow(),
+            <T as sealed::PressioElement>::DTYPE,
+            layout,
+            with,
+        )
+    }
+
+    /// Share the byte array with libpressio as data while `with` is called,
+    /// and report whether the array was shared or copied, see
+    /// [`PressioData::new_with_shared_or_copied`].
+    pub fn new_with_bytes_shared_or_copied<S: Data<Elem = c_uchar>, D: Dimension, O>(
+        x: impl Borrow<ArrayBase<S, D>>,
+        layout: PressioLayout,
+        with: impl for<'a> FnOnce(&'a Self) -> O,
+    ) -> (O, SharedOrCopied) {
+        Self::new_with_shared_or_copied_inner(
+            x.borrow(),
+            libpressio_sys::pressio_dtype_pressio_byte_dtype,
+            layout,
+            with,
+        )
+    }
+
+    /// Share the array with libpressio as data without ever copying it, see
+    /// [`PressioData::new_with_shared_or_copied`].
+    ///
+    /// An error is returned, and `with` is not called, if the array is
+    /// neither in standard layout nor Fortran-contiguous.
+    pub fn try_new_with_shared<T: PressioElement, S: Data<Elem = T>, D: Dimension, O>(
+        x: impl Borrow<ArrayBase<S, D>>,
+        layout: PressioLayout,
+        with: impl for<'a> FnOnce(&'a Self) -> O,
+    ) -> Result<O, PressioError> {
+        Self::try_new_with_shared_inner(
+            x.borrow(),
+            <T as sealed::PressioElement>::DTYPE,
+            layout,
+            with,
+        )
+    }
+
+    /// Share the byte array with libpressio as data without ever copying it,
+    /// see [`PressioData::try_new_with_shared`].
+    pub fn try_new_with_bytes_shared<S: Data<Elem = c_uchar>, D: Dimension, O>(
+        x: impl Borrow<ArrayBase<S, D>>,
+        layout: PressioLayout,
+        with: impl for<'a> FnOnce(&'a Self) -> O,
+    ) -> Result<O, PressioError> {
+        Self::try_new_with_shared_inner(
             x.borrow(),
             libpressio_sys::pressio_dtype_pressio_byte_dtype,
+            layout,
             with,
         )
     }
 
+    fn new_with_shared_or_copied_inner<T: Copy, S: Data<Elem = T>, D: Dimension, O>(
+        x: &ArrayBase<S, D>,
+        dtype: libpressio_sys::pressio_dtype,
+        layout: PressioLayout,
+        with: impl for<'a> FnOnce(&'a Self) -> O,
+    ) -> (O, SharedOrCopied) {
+        let sharing = SharedOrCopied::of(x, true);
+        let result = Self::new_with_shared_inner(x, dtype, layout, sharing, with);
+        (result, sharing)
+    }
+
+    fn try_new_with_shared_inner<T: Copy, S: Data<Elem = T>, D: Dimension, O>(
+        x: &ArrayBase<S, D>,
+        dtype: libpressio_sys::pressio_dtype,
+        layout: PressioLayout,
+        with: impl for<'a> FnOnce(&'a Self) -> O,
+    ) -> Result<O, PressioError> {
+        match SharedOrCopied::of(x, true) {
+            SharedOrCopied::Copied => Err(PressioError {
+                error_code: 1,
+                message: format!(
+                    "cannot share an array with shape {:?} and strides {:?} without copying",
+                    x.shape(),
+                    x.strides()
+                ),
+            }),
+            sharing => Ok(Self::new_with_shared_inner(x, dtype, layout, sharing, with)),
+        }
+    }
+
     fn new_with_shared_inner<T: Copy, S: Data<Elem = T>, D: Dimension, O>(
         x: &ArrayBase<S, D>,
         dtype: libpressio_sys::pressio_dtype,
+        layout: PressioLayout,
+        sharing: SharedOrCopied,
         with: impl for<'a> FnOnce(&'a Self) -> O,
     ) -> O {
-        if x.is_standard_layout() {
+        fn with_nonowning<O>(
+            ptr: *const c_void,
+            dtype: libpressio_sys::pressio_dtype,
+            shape: &[usize],
+            with: impl for<'a> FnOnce(&'a PressioData) -> O,
+        ) -> O {
             let data = unsafe {
                 libpressio_sys::pressio_data_new_nonowning(
                     dtype,
                     // SAFETY: we only give access to &PressioData, which does
                     //         not expose mutating access, so we can cast a
                     //         const ptr to a mut ptr here
-                    x.as_ptr().cast_mut().cast(),
-                    x.ndim(),
-                    x.shape().as_ptr(),
+                    ptr.cast_mut(),
+                    shape.len(),
+                    shape.as_ptr(),
                 )
             };
             let data = NonNull::new(data).expect("pressio_data_new_nonowning must not return null");
-            with(&Self { data })
-        } else {
-            let x_vec: Vec<T> = x.iter().copied().collect();
-            let data = unsafe {
-                libpressio_sys::pressio_data_new_nonowning(
+            with(&PressioData { data })
+        }
+
+        match sharing {
+            SharedOrCopied::Shared => with_nonowning(
+                x.as_ptr().cast(),
+                dtype,
+                &layout.convert_dims(x.shape()),
+                with,
+            ),
+            SharedOrCopied::SharedReversed => {
+                // the transposed view of a Fortran-contiguous array is in
+                //  standard layout
+                let x = x.t();
+                with_nonowning(
+                    x.as_ptr().cast(),
                     dtype,
-                    // SAFETY: we only give access to &PressioData, which does
-                    //         not expose mutating access, so we can cast a
-                    //         const ptr to a mut ptr here
-                    x_vec.as_ptr().cast_mut().cast(),
-                    x.ndim(),
-                    x.shape().as_ptr(),
+                    &layout.convert_dims(x.shape()),
+                    with,
                 )
-            };
-            let data = NonNull::new(data).expect("pressio_data_new_nonowning must not return null");
-            let result = with(&Self { data });
-            std::mem::drop(x_vec);
-            result
+            }
+            SharedOrCopied::Copied => {
+                let x_vec: Vec<T> = x.iter().copied().collect();
+                let result = with_nonowning(
+                    x_vec.as_ptr().cast(),
+                    dtype,
+                    &layout.convert_dims(x.shape()),
+                    with,
+                );
+                std::mem::drop(x_vec);
+                result
+            }
         }
     }
 
@@ -765,16 +1112,40 @@ impl PressioData {
         shape: impl Into<D>,
         with: impl for<'a> FnOnce(CowArray<'a, T, D>) -> O,
     ) -> Option<O> {
-        self.with_shared_inner(shape, with, <T as sealed::PressioElement>::DTYPE)
+        self.with_shared_with_layout(shape, PressioLayout::C, with)
+    }
+
+    /// Share the data as an array with the ndarray `shape`, which must match
+    /// the dimensions of the data when they are converted using the `layout`.
+    pub fn with_shared_with_layout<T: PressioElement, D: Dimension, O>(
+        &self,
+        shape: impl Into<D>,
+        layout: PressioLayout,
+        with: impl for<'a> FnOnce(CowArray<'a, T, D>) -> O,
+    ) -> Option<O> {
+        self.with_shared_inner(shape, layout, with, <T as sealed::PressioElement>::DTYPE)
     }
 
     pub fn with_shared_bytes<D: Dimension, O>(
         &self,
         shape: impl Into<D>,
         with: impl for<'a> FnOnce(CowArray<'a, c_uchar, D>) -> O,
+    ) -> Option<O> {
+        self.with_shared_bytes_with_layout(shape, PressioLayout::C, with)
+    }
+
+    /// Share the byte data as an array with the ndarray `shape`, which must
+    /// match the dimensions of the data when they are converted using the
+    /// `layout`.
+    pub fn with_shared_bytes_with_layout<D: Dimension, O>(
+        &self,
+        shape: impl Into<D>,
+        layout: PressioLayout,
+        with: impl for<'a> FnOnce(CowArray<'a, c_uchar, D>) -> O,
     ) -> Option<O> {
         self.with_shared_inner(
             shape,
+            layout,
             with,
             libpressio_sys::pressio_dtype_pressio_byte_dtype,
         )
@@ -783,6 +1154,7 @@ impl PressioData {
     fn with_shared_inner<T: Copy, D: Dimension, O>(
         &self,
         shape_out: impl Into<D>,
+        layout: PressioLayout,
         with: impl for<'a> FnOnce(CowArray<'a, T, D>) -> O,
         dtype_out: libpressio_sys::pressio_dtype,
     ) -> Option<O> {
@@ -797,7 +1169,7 @@ impl PressioData {
         }
 
         let shape_out = shape_out.into();
-        let shape = self.shape();
+        let shape = self.shape_with_layout(layout);
 
         if ArrayView::from(shape.as_slice()) != shape_out.as_array_view() {
             return None;
@@ -830,6 +1202,12 @@ impl PressioData {
     }
 
     pub fn clone_into_array(&self) -> Option<PressioArray> {
+        self.clone_into_array_with_layout(PressioLayout::C)
+    }
+
+    /// Copy the data into an array, whose shape is converted from the
+    /// dimensions of the data using the `layout`.
+    pub fn clone_into_array_with_layout(&self, layout: PressioLayout) -> Option<PressioArray> {
         fn clone_into_array_typed<T: Copy>(ptr: *const c_void, shape: &[usize]) -> Array<T, IxDyn> {
             let size: usize = shape.iter().product();
             // copy the data into a new vector, ensuring that our copy is
@@ -852,7 +1230,7 @@ impl PressioData {
         }
 
         let dtype = self.dtype()?;
-        let shape = self.shape();
+        let shape = self.shape_with_layout(layout);
 
         let mut num_bytes = 0;
         let ptr = unsafe { libpressio_sys::pressio_data_ptr(self.as_raw(), &raw mut num_bytes) }
@@ -904,6 +1282,12 @@ impl PressioData {
             .collect::<Vec<_>>()
     }
 
+    /// The ndarray shape of the data, which is converted from its libpressio
+    /// dimensions using the `layout`.
+    pub fn shape_with_layout(&self, layout: PressioLayout) -> Vec<usize> {
+        layout.convert_dims(&self.shape())
+    }
+
     pub fn cast(&self, dtype: PressioDtype) -> Self {
         let data_ptr =
             unsafe { libpressio_sys::pressio_data_cast(self.as_raw(), dtype.into_raw()) };
@@ -942,6 +1326,44 @@ impl PressioData {
         }
     }
 
+    /// Copy the `bytes` into new data with the given `dtype` and `shape`.
+    ///
+    /// The `bytes` must contain exactly the elements of the data in native
+    /// endianness.
+    fn from_bytes(dtype: PressioDtype, shape: &[usize], bytes: &[u8]) -> Self {
+        debug_assert_eq!(
+            bytes.len(),
+            shape.iter().product::<usize>() * dtype.size_in_bytes()
+        );
+        let data = unsafe {
+            libpressio_sys::pressio_data_new_copy(
+                dtype.into_raw(),
+                bytes.as_ptr().cast(),
+                shape.len(),
+                shape.as_ptr(),
+            )
+        };
+        let data = NonNull::new(data).expect("pressio_data_new_copy must not return null");
+        PressioData { data }
+    }
+
+    /// The raw bytes of the data, or [`None`] if it has no data.
+    fn as_bytes(&self) -> Option<&[u8]> {
+        if !self.has_data() {
+            return None;
+        }
+
+        let mut num_bytes = 0;
+        let ptr = unsafe { libpressio_sys::pressio_data_ptr(self.as_raw(), &raw mut num_bytes) };
+        if num_bytes == 0 {
+            return Some(&[]);
+        }
+
+        // Safety: the pointer is valid for num_bytes bytes while the data is
+        //         borrowed, and bytes are always aligned
+        Some(unsafe { std::slice::from_raw_parts(ptr.cast_const().cast::<u8>(), num_bytes) })
+    }
+
     fn as_raw(&self) -> *const libpressio_sys::pressio_data {
         self.data.as_ptr().cast_const()
     }
@@ -959,6 +1381,48 @@ impl Clone for PressioData {
     }
 }
 
+impl PartialEq for PressioData {
+    fn eq(&self, other: &Self) -> bool {
+        if self.dtype() != other.dtype()
+            || self.shape() != other.shape()
+            || self.has_data() != other.has_data()
+        {
+            return false;
+        }
+
+        if !self.has_data() {
+            return true;
+        }
+
+        let (mut self_num_bytes, mut other_num_bytes) = (0, 0);
+        let self_ptr =
+            unsafe { libpressio_sys::pressio_data_ptr(self.as_raw(), &raw mut self_num_bytes) };
+        let other_ptr =
+            unsafe { libpressio_sys::pressio_data_ptr(other.as_raw(), &raw mut other_num_bytes) };
+
+        if self_num_bytes != other_num_bytes {
+            return false;
+        }
+
+        // Safety: both pointers are valid for their number of bytes, and we
+        //         only compare the bytes, so alignment does not matter
+        unsafe {
+            std::slice::from_raw_parts(self_ptr.cast_const().cast::<u8>(), self_num_bytes)
+                == std::slice::from_raw_parts(other_ptr.cast_const().cast::<u8>(), other_num_bytes)
+        }
+    }
+}
+
+impl std::fmt::Debug for PressioData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PressioData")
+            .field("dtype", &self.dtype())
+            .field("shape", &self.shape())
+            .field("has_data", &self.has_data())
+            .finish()
+    }
+}
+
 impl Drop for PressioData {
     fn drop(&mut self) {
         unsafe {
@@ -1003,7 +1467,7 @@ impl std::fmt::Display for PressioThreadSafety {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 #[non_exhaustive]
 pub enum PressioOption {
     bool(Option<bool>),
@@ -1026,6 +1490,34 @@ pub enum PressioOption {
     unset,
 }
 
+/// Floating-point options are compared bitwise, such that a NaN value is
+/// equal to itself and `0.0` is not equal to `-0.0`.
+impl PartialEq for PressioOption {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::bool(a), Self::bool(b)) => a == b,
+            (Self::int8(a), Self::int8(b)) => a == b,
+            (Self::int16(a), Self::int16(b)) => a == b,
+            (Self::int32(a), Self::int32(b)) => a == b,
+            (Self::int64(a), Self::int64(b)) => a == b,
+            (Self::uint8(a), Self::uint8(b)) => a == b,
+            (Self::uint16(a), Self::uint16(b)) => a == b,
+            (Self::uint32(a), Self::uint32(b)) => a == b,
+            (Self::uint64(a), Self::uint64(b)) => a == b,
+            (Self::float32(a), Self::float32(b)) => a.map(f32::to_bits) == b.map(f32::to_bits),
+            (Self::float64(a), Self::float64(b)) => a.map(f64::to_bits) == b.map(f64::to_bits),
+            (Self::string(a), Self::string(b)) => a == b,
+            (Self::vec_string(a), Self::vec_string(b)) => a == b,
+            (Self::data(a), Self::data(b)) => a == b,
+            (Self::user_ptr(a), Self::user_ptr(b)) => a == b,
+            (Self::dtype(a), Self::dtype(b)) => a == b,
+            (Self::thread_safety(a), Self::thread_safety(b)) => a == b,
+            (Self::unset, Self::unset) => true,
+            _ => false,
+        }
+    }
+}
+
 impl PressioOption {
     /// Copy the option type without copying the option value.
     ///
@@ -1054,6 +1546,50 @@ impl PressioOption {
         }
     }
 
+    /// Convert a numeric option value to [`f64`].
+    ///
+    /// Returns [`None`] if the option is not numeric or has no value.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Self::bool(Some(x)) => Some(f64::from(u8::from(x))),
+            Self::int8(Some(x)) => Some(f64::from(x)),
+            Self::int16(Some(x)) => Some(f64::from(x)),
+            Self::int32(Some(x)) => Some(f64::from(x)),
+            Self::int64(Some(x)) => Some(x as f64),
+            Self::uint8(Some(x)) => Some(f64::from(x)),
+            Self::uint16(Some(x)) => Some(f64::from(x)),
+            Self::uint32(Some(x)) => Some(f64::from(x)),
+            Self::uint64(Some(x)) => Some(x as f64),
+            Self::float32(Some(x)) => Some(f64::from(x)),
+            Self::float64(Some(x)) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// The name of the option type, e.g. `float64`.
+    pub const fn type_name(&self) -> &'static str {
+        match self {
+            Self::bool(_) => "bool",
+            Self::int8(_) => "int8",
+            Self::int16(_) => "int16",
+            Self::int32(_) => "int32",
+            Self::int64(_) => "int64",
+            Self::uint8(_) => "uint8",
+            Self::uint16(_) => "uint16",
+            Self::uint32(_) => "uint32",
+            Self::uint64(_) => "uint64",
+            Self::float32(_) => "float32",
+            Self::float64(_) => "float64",
+            Self::string(_) => "string",
+            Self::vec_string(_) => "vec_string",
+            Self::data(_) => "data",
+            Self::user_ptr(_) => "user_ptr",
+            Self::dtype(_) => "dtype",
+            Self::thread_safety(_) => "thread_safety",
+            Self::unset => "unset",
+        }
+    }
+
     fn into_raw(self) -> Result<NonNull<libpressio_sys::pressio_option>, PressioError> {
         struct OptionDrop(NonNull<libpressio_sys::pressio_option>);
 
@@ -1412,6 +1948,51 @@ impl PressioOption {
     }
 }
 
+impl std::fmt::Display for PressioOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::bool(Some(x)) => write!(f, "{x}"),
+            Self::int8(Some(x)) => write!(f, "{x}"),
+            Self::int16(Some(x)) => write!(f, "{x}"),
+            Self::int32(Some(x)) => write!(f, "{x}"),
+            Self::int64(Some(x)) => write!(f, "{x}"),
+            Self::uint8(Some(x)) => write!(f, "{x}"),
+            Self::uint16(Some(x)) => write!(f, "{x}"),
+            Self::uint32(Some(x)) => write!(f, "{x}"),
+            Self::uint64(Some(x)) => write!(f, "{x}"),
+            Self::float32(Some(x)) => write!(f, "{x}"),
+            Self::float64(Some(x)) => write!(f, "{x}"),
+            Self::string(Some(x)) => f.write_str(x),
+            Self::vec_string(Some(x)) => write!(f, "[{}]", x.join(", ")),
+            Self::data(Some(x)) => match x.dtype() {
+                Some(dtype) => write!(f, "<{dtype} data {:?}>", x.shape()),
+                None => write!(f, "<data {:?}>", x.shape()),
+            },
+            Self::user_ptr(Some(x)) => write!(f, "{x:p}"),
+            Self::dtype(Some(x)) => write!(f, "{x}"),
+            Self::thread_safety(Some(x)) => write!(f, "{x}"),
+            Self::bool(None)
+            | Self::int8(None)
+            | Self::int16(None)
+            | Self::int32(None)
+            | Self::int64(None)
+            | Self::uint8(None)
+            | Self::uint16(None)
+            | Self::uint32(None)
+            | Self::uint64(None)
+            | Self::float32(None)
+            | Self::float64(None)
+            | Self::string(None)
+            | Self::vec_string(None)
+            | Self::data(None)
+            | Self::user_ptr(None)
+            | Self::dtype(None)
+            | Self::thread_safety(None)
+            | Self::unset => f.write_str("<unset>"),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PressioConversionSafety {
     Implicit,
@@ -1611,6 +2192,20 @@ impl PressioOptions {
     }
 
     pub fn iter(&self) -> impl FusedIterator<Item = (Option<String>, Option<PressioOption>)> + '_ {
+        self.raw_iter()
+    }
+
+    /// Iterate over the `(key, option)` entries.
+    ///
+    /// Unlike [`PressioOptions::iter`], an error is yielded for entries with
+    /// a non-UTF-8 key or an unsupported option type.
+    pub fn entries(&self) -> PressioOptionsEntries<'_> {
+        PressioOptionsEntries {
+            iter: self.raw_iter(),
+        }
+    }
+
+    fn raw_iter(&self) -> PressioOptionsIter<'_> {
         // Safety:
         // - self.ptr is a valid pointer to options
         // - we hold an immutable reference to ensure the iterator is not
@@ -1623,7 +2218,7 @@ impl PressioOptions {
         PressioOptionsIter {
             ptr,
             // the _marker inherits the lifetimes of &self
-            //  (see the +'_ in the return signature)
+            //  (see the '_ in the return signature)
             // to ensure that
             // - self must outlive the returned PressioOptionsIter
             // - self is immutably borrowed until the PressioOptionsIter is
@@ -1669,6 +2264,12 @@ impl std::fmt::Display for PressioOptions {
     }
 }
 
+impl std::fmt::Debug for PressioOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
 struct PressioOptionsIter<'a> {
     // pressio_options_iter is Send but !Sync
     // - impl Send below
@@ -1691,10 +2292,8 @@ impl PressioOptionsIter<'_> {
     }
 }
 
-impl Iterator for PressioOptionsIter<'_> {
-    type Item = (Option<String>, Option<PressioOption>);
-
-    fn next(&mut self) -> Option<Self::Item> {
+impl PressioOptionsIter<'_> {
+    fn next_raw(&mut self) -> Option<(Result<String, IntoStringError>, Option<PressioOption>)> {
         if !unsafe { libpressio_sys::pressio_options_iter_has_value(self.as_raw_mut()) } {
             return None;
         }
@@ -1711,7 +2310,7 @@ impl Iterator for PressioOptionsIter<'_> {
 
         let option_ptr =
             unsafe { libpressio_sys::pressio_options_iter_get_value(self.as_raw_mut()) };
-        let option_key = option_key.into_string().ok();
+        let option_key = option_key.into_string();
 
         // Safety: option_ptr is a valid pointer to an option
         let option = PressioOption::from_raw(option_ptr.cast_const());
@@ -1728,6 +2327,15 @@ impl Iterator for PressioOptionsIter<'_> {
     }
 }
 
+impl Iterator for PressioOptionsIter<'_> {
+    type Item = (Option<String>, Option<PressioOption>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (option_key, option) = self.next_raw()?;
+        Some((option_key.ok(), option))
+    }
+}
+
 impl FusedIterator for PressioOptionsIter<'_> {}
 
 impl Drop for PressioOptionsIter<'_> {
@@ -1736,15 +2344,165 @@ impl Drop for PressioOptionsIter<'_> {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct PressioOptionsEntries<'a> {
+    iter: PressioOptionsIter<'a>,
+}
 
-    fn input_data() -> ndarray::ArrayD<f32> {
-        ndarray::Array2::from_shape_fn((30, 30), |(x, y)| (x + y) as f32).into_dyn()
+impl Iterator for PressioOptionsEntries<'_> {
+    type Item = Result<(String, PressioOption), PressioError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (option_key, option) = self.iter.next_raw()?;
+
+        let option_key = match option_key {
+            Ok(option_key) => option_key,
+            Err(err) => {
+                return Some(Err(PressioError::utf8_error(
+                    err.utf8_error(),
+                    "option key",
+                )));
+            }
+        };
+
+        match option {
+            Some(option) => Some(Ok((option_key, option))),
+            None => Some(Err(PressioError {
+                error_code: 1,
+                message: format!("option `{option_key}` has an unsupported type"),
+            })),
+        }
     }
+}
 
-    fn safe_works(
+impl FusedIterator for PressioOptionsEntries<'_> {}
+
+impl<'a> IntoIterator for &'a PressioOptions {
+    type Item = Result<(String, PressioOption), PressioError>;
+    type IntoIter = PressioOptionsEntries<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries()
+    }
+}
+
+pub struct PressioOptionsIntoIter {
+    // the entries borrow the options, so they must be dropped first
+    entries: PressioOptionsEntries<'static>,
+    _options: PressioOptions,
+}
+
+impl Iterator for PressioOptionsIntoIter {
+    type Item = Result<(String, PressioOption), PressioError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next()
+    }
+}
+
+impl FusedIterator for PressioOptionsIntoIter {}
+
+impl IntoIterator for PressioOptions {
+    type Item = Result<(String, PressioOption), PressioError>;
+    type IntoIter = PressioOptionsIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        // Safety:
+        // - the entries only point into the heap-allocated pressio_options,
+        //   which does not move when the PressioOptions is moved
+        // - PressioOptionsIntoIter owns the options, never hands out access to
+        //   them, and drops the entries before the options
+        let entries = unsafe {
+            std::mem::transmute::<PressioOptionsEntries<'_>, PressioOptionsEntries<'static>>(
+                self.entries(),
+            )
+        };
+
+        PressioOptionsIntoIter {
+            entries,
+            _options: self,
+        }
+    }
+}
+
+/// # Panics
+///
+/// Panics if the options cannot be allocated or if a key contains a null
+/// byte. Collect an iterator of [`Result`]s or use [`PressioOptions::set`] to
+/// handle these errors instead.
+impl<K: AsRef<str>> FromIterator<(K, PressioOption)> for PressioOptions {
+    fn from_iter<I: IntoIterator<Item = (K, PressioOption)>>(iter: I) -> Self {
+        let mut options = Self::new().expect("failed to allocate options");
+        options.extend(iter);
+        options
+    }
+}
+
+/// # Panics
+///
+/// Panics if a key contains a null byte. Use [`PressioOptions::set`] to
+/// handle this error instead.
+impl<K: AsRef<str>> Extend<(K, PressioOption)> for PressioOptions {
+    fn extend<I: IntoIterator<Item = (K, PressioOption)>>(&mut self, iter: I) {
+        for (key, option) in iter {
+            let key = key.as_ref();
+            if let Err(err) = self.set(key, option) {
+                panic!("failed to set option `{key}`: {err}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input_data() -> ndarray::ArrayD<f32> {
+        ndarray::Array2::from_shape_fn((30, 30), |(x, y)| (x + y) as f32).into_dyn()
+    }
+
+    /// The first lossy compressor in this build that accepts an absolute
+    /// error bound and the [`input_data`], wrapped in the `pressio`
+    /// meta-compressor with the `metric` enabled.
+    fn lossy_compressor(
+        lib: &mut Pressio,
+        metric: &str,
+    ) -> Result<Option<(&'static str, PressioCompressor)>, PressioError> {
+        let input = PressioData::new_copied(input_data());
+
+        for id in supported_compressors()? {
+            if matches!(id, "noop" | "pressio") {
+                continue;
+            }
+            let mut compressor = lib.get_compressor(id)?;
+            if !compressor.supports_error_bound(ErrorBound::Absolute(1.0))? {
+                continue;
+            }
+            compressor.set_error_bound(ErrorBound::Absolute(1.0))?;
+            if compressor
+                .compress(&input, PressioData::new_empty(PressioDtype::Byte, []))
+                .is_err()
+            {
+                continue;
+            }
+
+            let mut meta = lib.get_compressor("pressio")?;
+            let mut options = PressioOptions::new()?;
+            options.set(
+                "pressio:compressor",
+                PressioOption::string(Some(String::from(id))),
+            )?;
+            options.set(
+                "pressio:metric",
+                PressioOption::string(Some(String::from(metric))),
+            )?;
+            meta.set_options(&options)?;
+            return Ok(Some((id, meta)));
+        }
+
+        Ok(None)
+    }
+
+    fn safe_works(
         ndarray_to_data: impl Fn(
             ndarray::ArrayD<f32>,
             &mut PressioCompressor,
@@ -1852,6 +2610,1246 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn schema_of_every_compressor() -> Result<(), PressioError> {
+        let mut lib = Pressio::new()?;
+
+        for id in supported_compressors()? {
+            let compressor = lib.get_compressor(id)?;
+            let schema = compressor.get_schema()?;
+            assert_eq!(schema.prefix, compressor.get_prefix()?);
+
+            let options = compressor.get_options()?;
+            let namespaces = options.namespaces();
+            for entry in schema.tunable() {
+                assert!(options.has_option(&entry.key)?);
+                assert_eq!(
+                    entry.namespace.as_deref(),
+                    entry.key.split_once(':').map(|(namespace, _)| namespace)
+                );
+                if let Some(namespace) = &entry.namespace {
+                    assert!(namespaces.contains(namespace));
+                    assert!(options.subtree(namespace)?.has_option(&entry.name)?);
+                }
+            }
+        }
+
+        let compressor = lib.get_compressor("pressio")?;
+        let schema = compressor.get_schema()?;
+        let entry = schema
+            .get("pressio:thread_safe")
+            .expect("pressio exposes its thread safety");
+        assert_eq!(entry.kind, PressioOptionKind::Configuration);
+        assert!(matches!(
+            entry.option_type,
+            PressioOption::thread_safety(None)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn options_namespaces() -> Result<(), PressioError> {
+        let mut options = PressioOptions::new()?;
+        options.set("sz:abs_err_bound", PressioOption::float64(Some(1e-4)))?;
+        options.set(
+            "pressio:metric",
+            PressioOption::string(Some(String::from("size"))),
+        )?;
+        options.set("pressio:sz:abs_err_bound", PressioOption::float64(None))?;
+
+        assert_eq!(options.namespaces(), ["pressio", "sz"]);
+
+        let pressio = options.subtree("pressio")?;
+        assert_eq!(pressio.len(), 2);
+        assert!(pressio.has_option("metric")?);
+        assert!(pressio.has_option("sz:abs_err_bound")?);
+        assert!(!pressio.is_option_set("sz:abs_err_bound")?);
+
+        let rerooted = options.subtree("pressio:sz")?.with_prefix("blosc")?;
+        assert_eq!(rerooted.len(), 1);
+        assert!(rerooted.has_option("blosc:abs_err_bound")?);
+
+        let tree = options.tree();
+        assert!(matches!(
+            tree.get("sz:abs_err_bound")
+                .and_then(|node| node.value.as_ref()),
+            Some(PressioOption::float64(Some(1e-4)))
+        ));
+        assert_eq!(
+            tree.walk().map(|(key, _, _)| key).collect::<Vec<_>>(),
+            [
+                "pressio:metric",
+                "pressio:sz:abs_err_bound",
+                "sz:abs_err_bound"
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn options_diff() -> Result<(), PressioError> {
+        let mut before = PressioOptions::new()?;
+        before.set("pressio:abs", PressioOption::float64(Some(1e-4)))?;
+        before.set("pressio:lossless", PressioOption::int32(None))?;
+        before.set("sz:mode", PressioOption::uint32(Some(0)))?;
+
+        let mut after = before.clone();
+        assert_eq!(before, after);
+        assert!(before.diff(&after).is_empty());
+
+        before.set("sz:rel", PressioOption::float32(Some(f32::NAN)))?;
+        after.set("sz:rel", PressioOption::float32(Some(f32::NAN)))?;
+        assert!(before.diff(&after).is_empty());
+
+        after.set("pressio:abs", PressioOption::float64(Some(1e-3)))?;
+        after.set("pressio:metric", PressioOption::string(None))?;
+
+        let mut options = PressioOptions::new()?;
+        options.set("pressio:abs", PressioOption::float64(Some(1e-3)))?;
+        options.set("pressio:lossless", PressioOption::int32(None))?;
+        options.set("pressio:metric", PressioOption::string(None))?;
+        options.set("sz:rel", PressioOption::float32(Some(f32::NAN)))?;
+
+        assert_ne!(before, after);
+        assert_eq!(
+            before.diff(&after),
+            OptionsDiff {
+                added: vec![(String::from("pressio:metric"), PressioOption::string(None))],
+                removed: vec![],
+                changed: vec![OptionChange {
+                    key: String::from("pressio:abs"),
+                    old: PressioOption::float64(Some(1e-4)),
+                    new: PressioOption::float64(Some(1e-3)),
+                }],
+            }
+        );
+        assert_eq!(
+            after.diff(&options).removed,
+            [(String::from("sz:mode"), PressioOption::uint32(Some(0)))]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_option_assignments() -> Result<(), PressioError> {
+        let mut lib = Pressio::new()?;
+        let compressor = lib.get_compressor("pressio")?;
+        let template = compressor.get_options()?;
+
+        let options = template.parse_assignments(["pressio:abs=1e-4", "pressio:metric=size"])?;
+        assert_eq!(options.len(), 2);
+        assert_eq!(
+            options.get("pressio:abs")?,
+            Some(PressioOption::float64(Some(1e-4)))
+        );
+        assert_eq!(
+            options.get("pressio:metric")?,
+            Some(PressioOption::string(Some(String::from("size"))))
+        );
+
+        let err = template.parse_assignments(["abs=1e-4"]).unwrap_err();
+        assert!(err.message.contains("`pressio:abs`"), "{}", err.message);
+
+        assert!(template.parse_assignments(["pressio:abs"]).is_err());
+        assert!(template.parse_assignments(["pressio:abs=tiny"]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn options_collections() -> Result<(), PressioError> {
+        use std::collections::BTreeMap;
+
+        let map = BTreeMap::from([
+            ("pressio:abs", PressioOption::float64(Some(1e-4))),
+            ("pressio:lossless", PressioOption::int32(None)),
+        ]);
+
+        let mut options = map.clone().into_iter().collect::<PressioOptions>();
+        assert_eq!(options.len(), 2);
+
+        options.extend([(
+            "pressio:metric",
+            PressioOption::string(Some(String::from("size"))),
+        )]);
+
+        let entries = (&options)
+            .into_iter()
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            entries.get("pressio:abs"),
+            Some(&PressioOption::float64(Some(1e-4)))
+        );
+
+        let roundtrip = options
+            .into_iter()
+            .filter(|entry| !matches!(entry, Ok((key, _)) if key == "pressio:metric"))
+            .collect::<Result<PressioOptions, _>>()?;
+        assert_eq!(roundtrip, map.into_iter().collect());
+
+        Ok(())
+    }
+
+    #[test]
+    fn set_error_bound() -> Result<(), PressioError> {
+        let mut lib = Pressio::new()?;
+
+        let mut compressor = lib.get_compressor("pressio")?;
+        assert!(compressor.supports_error_bound(ErrorBound::Absolute(1e-4))?);
+        compressor.set_error_bound(ErrorBound::Absolute(1e-4))?;
+        assert_eq!(
+            compressor.get_options()?.get("pressio:abs")?,
+            Some(PressioOption::float64(Some(1e-4)))
+        );
+
+        let mut noop = lib.get_compressor("noop")?;
+        assert!(!noop.supports_error_bound(ErrorBound::FixedRate(8.0))?);
+        let err = noop
+            .set_error_bound(ErrorBound::FixedRate(8.0))
+            .unwrap_err();
+        assert!(err.message.contains("`noop:rate`"), "{}", err.message);
+
+        Ok(())
+    }
+
+    #[test]
+    fn ratio_search() -> Result<(), PressioError> {
+        let mut lib = Pressio::new()?;
+        // the search needs a compressor whose ratio grows with the bound
+        let Some((id, mut compressor)) = lossy_compressor(&mut lib, "size")? else {
+            return Ok(());
+        };
+
+        let input = PressioData::new_copied(input_data());
+
+        let mut search = RatioSearch::new(SearchMode::Absolute, 4.0);
+        search.tolerance = 0.1;
+        let result = search.run(&mut compressor, &input)?;
+
+        assert!((2..=search.max_iterations).contains(&result.trace.len()));
+        assert!(result.trace.contains(&RatioSearchStep {
+            bound: result.bound,
+            ratio: result.ratio,
+        }));
+        assert!(result.converged, "{id}: {:?}", result.trace);
+        assert!(
+            (result.ratio / 4.0 - 1.0).abs() <= search.tolerance,
+            "{id}: ratio {}",
+            result.ratio
+        );
+        assert!(result.compressed.has_data());
+        assert_eq!(
+            compressor.get_options()?.get("pressio:abs")?,
+            Some(PressioOption::float64(result.bound.value()))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn quality_search() -> Result<(), PressioError> {
+        let mut lib = Pressio::new()?;
+        // the search needs a compressor whose error grows with the bound
+        let Some((id, mut compressor)) = lossy_compressor(&mut lib, "error_stat")? else {
+            return Ok(());
+        };
+
+        let input = PressioData::new_copied(input_data());
+
+        let mut search = QualitySearch::new(SearchMode::Absolute, QualityMetric::MaxAbsError, 0.5);
+        search.max_iterations = 8;
+        let result = search.run(&mut compressor, &input)?;
+
+        assert!((1..=8).contains(&result.trace.len()));
+        assert!(result.trace.iter().all(|step| !step.cached));
+
+        let (Some(bound), Some(value)) = (result.bound, result.value) else {
+            panic!(
+                "{id} meets the threshold at the smallest bound: {:?}",
+                result.trace
+            );
+        };
+        assert!(value <= 0.5, "{id}: max error {value}");
+        assert!(bound.value().is_some_and(|bound| bound > 0.0));
+        assert_eq!(
+            compressor.get_options()?.get("pressio:abs")?,
+            Some(PressioOption::float64(bound.value()))
+        );
+
+        let rerun = search.run(&mut compressor, &input)?;
+        assert_eq!(rerun.bound, result.bound);
+        assert!(rerun.trace.iter().all(|step| step.cached));
+
+        Ok(())
+    }
+
+    #[test]
+    fn option_grid_points() -> Result<(), PressioError> {
+        let grid = OptionGrid::new()
+            .axis(
+                "pressio:abs",
+                [1e-2, 1e-4].map(|bound| PressioOption::float64(Some(bound))),
+            )
+            .axis(
+                "sz:mode",
+                [0, 1, 2].map(|mode| PressioOption::uint32(Some(mode))),
+            );
+
+        let points = grid.points()?.collect::<Vec<_>>();
+        assert_eq!(points.len(), 6);
+        assert_eq!(
+            points[1],
+            [
+                (
+                    String::from("pressio:abs"),
+                    PressioOption::float64(Some(1e-2))
+                ),
+                (String::from("sz:mode"), PressioOption::uint32(Some(1))),
+            ]
+        );
+        assert_eq!(OptionGrid::new().points()?.collect::<Vec<_>>(), [vec![]]);
+        assert!(grid.axis("pressio:rel", []).points().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn bench_noop() -> Result<(), PressioError> {
+        let mut lib = Pressio::new()?;
+        let input = PressioData::new_copied(input_data());
+
+        let mut bench = Bench::new()
+            .case(
+                "noop",
+                OptionGrid::new().axis(
+                    "pressio:abs",
+                    [1e-2, 1e-4].map(|bound| PressioOption::float64(Some(bound))),
+                ),
+            )
+            .case("does-not-exist", OptionGrid::new());
+        bench.repetitions = 2;
+        bench.metrics = vec![String::from("size")];
+
+        let report = bench.run(&mut lib, &input)?;
+
+        assert_eq!(report.records.len(), 2 * 2);
+        for record in &report.records {
+            assert_eq!(record.compressor_id, "noop");
+            assert!(record.compression_ratio.is_finite());
+            assert!(record.compression_ratio > 0.0);
+            assert!(record.metrics.contains_key("size:compression_ratio"));
+        }
+        assert!(report.metric_keys().contains("size:compression_ratio"));
+
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].compressor_id, "does-not-exist");
+
+        assert_eq!(
+            Bench::all_compressors()?.cases.len(),
+            supported_compressors()?.len()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn report_formats() -> std::io::Result<()> {
+        let report = BenchReport {
+            records: vec![BenchRecord {
+                compressor_id: String::from("sz"),
+                options: vec![(String::from("pressio:abs"), String::from("0.01"))],
+                repetition: 0,
+                compress_seconds: 0.5,
+                decompress_seconds: 0.25,
+                compress_throughput: 2e6,
+                decompress_throughput: 4e6,
+                compression_ratio: 8.0,
+                metrics: std::collections::BTreeMap::from([(
+                    String::from("error_stat:psnr"),
+                    f64::INFINITY,
+                )]),
+            }],
+            failures: vec![BenchFailure {
+                compressor_id: String::from("noop"),
+                options: Vec::new(),
+                error: PressioError {
+                    error_code: 1,
+                    message: String::from("unsupported \"dtype\""),
+                },
+            }],
+        };
+
+        let mut csv = Vec::new();
+        report.write_csv(&mut csv)?;
+        assert_eq!(
+            String::from_utf8_lossy(&csv),
+            "compressor,options,repetition,compress_seconds,decompress_seconds,compress_throughput,decompress_throughput,compression_ratio,error_stat:psnr\n\
+             sz,pressio:abs=0.01,0,0.5,0.25,2000000,4000000,8,inf\n"
+        );
+
+        let mut json = Vec::new();
+        report.write_json(&mut json)?;
+        let json = String::from_utf8_lossy(&json);
+        assert!(json.contains("\"metrics\": {\"error_stat:psnr\": null}"));
+        assert!(json.contains("\"message\": \"unsupported \\\"dtype\\\"\""));
+
+        assert!(report.to_string().contains("8.000"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn verify_roundtrip_noop() -> Result<(), PressioError> {
+        let mut lib = Pressio::new()?;
+        let mut compressor = lib.get_compressor("noop")?;
+
+        let input = PressioData::new_copied(ndarray::array![[1_i64, 2, 3], [4, 5, 6]]);
+        let report = verify_roundtrip(&mut compressor, &input, ErrorBound::Lossless)?;
+
+        assert!(report.is_ok(), "{:?}", report.worst);
+        assert_eq!(report.num_elements, 6);
+        assert_eq!(report.max_abs_error, 0.0);
+        assert_eq!(report.decompressed, input);
+
+        let input = PressioData::new_copied(input_data());
+        let report = verify_roundtrip(&mut compressor, &input, ErrorBound::Absolute(0.0))?;
+        assert!(report.is_ok(), "{:?}", report.worst);
+        assert_eq!(report.decompressed.shape(), vec![30, 30]);
+
+        assert!(verify_roundtrip(&mut compressor, &input, ErrorBound::FixedRate(8.0)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn masked_roundtrip() -> Result<(), PressioError> {
+        let mut lib = Pressio::new()?;
+        let mut compressor = lib.get_compressor("noop")?;
+        let mut mask_compressor = lib.get_compressor("noop")?;
+
+        let array = ndarray::array![[1.0_f32, f32::NAN, 3.0], [-9999.0, f32::NEG_INFINITY, 5.0]];
+        let input = PressioData::new_copied(&array);
+        let policy = MaskPolicy {
+            fill_value: Some(-9999.0),
+            ..MaskPolicy::default()
+        };
+
+        let compressed = compressor.compress_masked(&input, policy, &mut mask_compressor)?;
+        assert_eq!(compressed.num_masked, 3);
+        assert!(compressed.mask.is_some());
+
+        let decompressed = compressor.decompress_masked(
+            &compressed,
+            PressioData::new_empty(PressioDtype::F32, [2, 3]),
+            &mut mask_compressor,
+        )?;
+        let Some(PressioArray::F32(output)) = decompressed.clone_into_array() else {
+            panic!("decompressed data must be f32");
+        };
+        assert_eq!(output.shape(), &[2, 3]);
+        for (x, y) in output.iter().zip(&array) {
+            assert_eq!(x.to_bits(), y.to_bits());
+        }
+
+        let input = PressioData::new_copied(ndarray::array![1_i32, 2, 3]);
+        let compressed =
+            compressor.compress_masked(&input, MaskPolicy::default(), &mut mask_compressor)?;
+        assert_eq!(compressed.num_masked, 0);
+        assert!(compressed.mask.is_none());
+
+        assert!(
+            compressor
+                .compress_masked(
+                    &PressioData::new_copied(ndarray::array![true]),
+                    MaskPolicy::default(),
+                    &mut mask_compressor
+                )
+                .is_err()
+        );
+
+        // unmasked NaN values do not leak into the replacement value
+        let input = PressioData::new_copied(ndarray::array![f64::NAN, 1.0, -9999.0, 3.0]);
+        let policy = MaskPolicy {
+            nan: false,
+            inf: true,
+            fill_value: Some(-9999.0),
+        };
+        let compressed = compressor.compress_masked(&input, policy, &mut mask_compressor)?;
+        assert_eq!(compressed.num_masked, 1);
+        let field = compressor.decompress(
+            &compressed.field,
+            PressioData::new_empty(PressioDtype::F64, [4]),
+        )?;
+        let Some(PressioArray::F64(field)) = field.clone_into_array() else {
+            panic!("decompressed field must be f64");
+        };
+        assert_eq!(field[2], 2.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn masked_roundtrip_lossy() -> Result<(), PressioError> {
+        let mut lib = Pressio::new()?;
+        let mut mask_compressor = lib.get_compressor("noop")?;
+
+        let bound = 0.5;
+        let mut array = input_data();
+        array[[0, 0]] = f32::NAN;
+        array[[3, 7]] = f32::INFINITY;
+        array[[12, 5]] = f32::NEG_INFINITY;
+        array[[29, 29]] = -9999.0;
+        let input = PressioData::new_copied(&array);
+        let policy = MaskPolicy {
+            fill_value: Some(-9999.0),
+            ..MaskPolicy::default()
+        };
+
+        // test every lossy compressor in this build that accepts an absolute
+        //  error bound and the uncompressed input
+        for id in supported_compressors()? {
+            if matches!(id, "noop" | "pressio") {
+                continue;
+            }
+            let mut compressor = lib.get_compressor(id)?;
+            if !compressor.supports_error_bound(ErrorBound::Absolute(bound))? {
+                continue;
+            }
+            compressor.set_error_bound(ErrorBound::Absolute(bound))?;
+            let plain = PressioData::new_copied(input_data());
+            if compressor
+                .compress(&plain, PressioData::new_empty(PressioDtype::Byte, []))
+                .is_err()
+            {
+                continue;
+            }
+
+            let compressed = compressor.compress_masked(&input, policy, &mut mask_compressor)?;
+            assert_eq!(compressed.num_masked, 4, "{id}");
+
+            let decompressed = compressor.decompress_masked(
+                &compressed,
+                PressioData::new_empty(PressioDtype::F32, [30, 30]),
+                &mut mask_compressor,
+            )?;
+            let Some(PressioArray::F32(output)) = decompressed.clone_into_array() else {
+                panic!("{id} must decompress into f32 data");
+            };
+
+            for (x, y) in output.iter().zip(&array) {
+                if y.is_finite() && *y != -9999.0 {
+                    assert!((x - y).abs() <= bound as f32, "{id}: {x} vs {y}");
+                } else {
+                    assert_eq!(x.to_bits(), y.to_bits(), "{id}");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn io_posix_roundtrip() -> Result<(), PressioError> {
+        let path = std::env::temp_dir().join(format!("libpressio-io-{}.f32", std::process::id()));
+
+        let mut lib = Pressio::new()?;
+        let mut io = lib.get_io("posix")?;
+        assert_eq!(io.get_prefix()?, "posix");
+
+        let mut options = PressioOptions::new()?;
+        options.set(
+            "io:path",
+            PressioOption::string(path.to_str().map(String::from)),
+        )?;
+        io.set_options(&options)?;
+
+        let data = PressioData::new_copied(input_data());
+        io.write(&data)?;
+        assert_eq!(
+            std::fs::metadata(&path).map(|metadata| metadata.len()).ok(),
+            Some(30 * 30 * 4)
+        );
+
+        let read = io.read(Some(PressioData::new_empty(PressioDtype::F32, [30, 30])))?;
+        assert_eq!(read, data);
+
+        let bytes = io.read(None)?;
+        assert_eq!(bytes.dtype(), Some(PressioDtype::Byte));
+        assert_eq!(bytes.num_bytes(), 30 * 30 * 4);
+
+        let _ = std::fs::remove_file(&path);
+
+        Ok(())
+    }
+
+    #[test]
+    fn npy_roundtrip() -> Result<(), PressioError> {
+        let array =
+            ndarray::Array::from_shape_fn((2, 3, 4), |(i, j, k)| (i * 100 + j * 10 + k) as f64);
+        let data = PressioData::new_copied(&array);
+
+        let mut npy = Vec::new();
+        data.write_npy_to(&mut npy)?;
+        assert_eq!((npy.len() - 2 * 3 * 4 * 8) % 64, 0);
+
+        let read = PressioData::read_npy_from(npy.as_slice())?;
+        assert_eq!(read.shape(), vec![2, 3, 4]);
+        assert_eq!(read, data);
+
+        // big-endian [[1, 2, 3], [4, 5, 6]] in Fortran order
+        let header = "{'descr': '>i2', 'fortran_order': True, 'shape': (2, 3), }";
+        let mut npy = b"\x93NUMPY\x01\x00".to_vec();
+        npy.extend_from_slice(&u16::try_from(header.len()).unwrap().to_le_bytes());
+        npy.extend_from_slice(header.as_bytes());
+        for x in [1_i16, 4, 2, 5, 3, 6] {
+            npy.extend_from_slice(&x.to_be_bytes());
+        }
+
+        let read = PressioData::read_npy_from(npy.as_slice())?;
+        assert_eq!(
+            read.clone_into_array(),
+            Some(PressioArray::I16(
+                ndarray::array![[1, 2, 3], [4, 5, 6]].into_dyn()
+            ))
+        );
+        assert!(PressioData::read_npy_from(&npy[..npy.len() - 1]).is_err());
+
+        let data = PressioData::new_copied(ndarray::array![true, false, true]);
+        let mut npy = Vec::new();
+        data.write_npy_to(&mut npy)?;
+        assert_eq!(PressioData::read_npy_from(npy.as_slice())?, data);
+
+        if let Some(last) = npy.last_mut() {
+            *last = 2;
+        }
+        assert!(PressioData::read_npy_from(npy.as_slice()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "npz")]
+    fn npz_roundtrip() -> Result<(), PressioError> {
+        let arrays = [
+            ("input", PressioData::new_copied(input_data())),
+            (
+                "counts",
+                PressioData::new_copied(ndarray::array![[1_i16, -2], [3, -4], [5, -6]]),
+            ),
+            (
+                "mask",
+                PressioData::new_copied(ndarray::array![true, false, true]),
+            ),
+            (
+                "cube",
+                PressioData::new_copied(ndarray::Array::from_shape_fn((2, 3, 4), |(i, j, k)| {
+                    (i * 12 + j * 4 + k) as u8
+                })),
+            ),
+        ];
+
+        for compressed in [false, true] {
+            let path = std::env::temp_dir().join(format!(
+                "libpressio-{}-{compressed}.npz",
+                std::process::id()
+            ));
+
+            write_npz(
+                &path,
+                arrays.iter().map(|(name, data)| (*name, data)),
+                compressed,
+            )?;
+            let read = read_npz(&path);
+            let _ = std::fs::remove_file(&path);
+            let read = read?;
+
+            assert_eq!(read.len(), arrays.len());
+            for ((name, data), (read_name, read_data)) in arrays.iter().zip(&read) {
+                assert_eq!(read_name, name);
+                assert_eq!(read_data.dtype(), data.dtype());
+                assert_eq!(read_data.shape(), data.shape());
+                assert_eq!(read_data.as_bytes(), data.as_bytes());
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn npy_parse_header() -> Result<(), PressioError> {
+        assert_eq!(
+            npy::NpyHeader::parse("{'descr': '<f4', 'fortran_order': False, 'shape': (3, 4), }")?,
+            npy::NpyHeader {
+                dtype: PressioDtype::F32,
+                big_endian: false,
+                fortran_order: false,
+                shape: vec![3, 4],
+            }
+        );
+        assert_eq!(
+            npy::NpyHeader::parse("{'descr': '>i8', 'fortran_order': True, 'shape': (5,), }")?,
+            npy::NpyHeader {
+                dtype: PressioDtype::I64,
+                big_endian: true,
+                fortran_order: true,
+                shape: vec![5],
+            }
+        );
+        assert_eq!(
+            npy::NpyHeader::parse("{'descr': '|b1', 'fortran_order': False, 'shape': (), }")?.shape,
+            vec![1]
+        );
+        assert!(
+            npy::NpyHeader::parse("{'descr': '<c8', 'fortran_order': False, 'shape': (2,), }")
+                .is_err()
+        );
+        assert!(
+            npy::NpyHeader::parse(
+                "{'descr': [('x', '<f4')], 'fortran_order': False, 'shape': (2,), }"
+            )
+            .is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn npy_fortran_order() {
+        // [[0, 1, 2], [3, 4, 5]] in column-major order
+        let bytes = [0, 3, 1, 4, 2, 5];
+        assert_eq!(
+            npy::fortran_to_c_order(&bytes, &[2, 3], 1),
+            [0, 1, 2, 3, 4, 5]
+        );
+
+        let bytes = [0, 0, 3, 3, 1, 1, 4, 4, 2, 2, 5, 5];
+        assert_eq!(
+            npy::fortran_to_c_order(&bytes, &[2, 3], 2),
+            [0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5]
+        );
+    }
+
+    #[test]
+    fn dimension_layout() -> Result<(), PressioError> {
+        let array =
+            ndarray::Array::from_shape_fn((2, 3, 4), |(i, j, k)| (i * 100 + j * 10 + k) as i32)
+                .into_dyn();
+
+        let c = PressioData::new_copied_with_layout(&array, PressioLayout::C);
+        let fortran = PressioData::new_copied_with_layout(&array, PressioLayout::Fortran);
+        assert_eq!(c.shape(), vec![2, 3, 4]);
+        assert_eq!(fortran.shape(), vec![4, 3, 2]);
+        assert_eq!(
+            fortran.shape_with_layout(PressioLayout::Fortran),
+            vec![2, 3, 4]
+        );
+
+        // the layout only changes the dimensions, not the memory
+        assert_eq!(c.as_bytes(), fortran.as_bytes());
+
+        assert_eq!(
+            fortran.clone_into_array_with_layout(PressioLayout::Fortran),
+            Some(PressioArray::I32(array.clone()))
+        );
+        assert_eq!(
+            fortran.clone_into_array().map(|array| array.dtype()),
+            Some(PressioDtype::I32)
+        );
+        assert_eq!(
+            fortran.with_shared_with_layout(
+                ndarray::IxDyn(&[2, 3, 4]),
+                PressioLayout::Fortran,
+                |shared: CowArray<i32, IxDyn>| shared.to_owned()
+            ),
+            Some(array.clone())
+        );
+        assert!(
+            fortran
+                .with_shared(ndarray::IxDyn(&[2, 3, 4]), |_: CowArray<i32, IxDyn>| ())
+                .is_none()
+        );
+
+        PressioData::new_with_shared_with_layout(&array, PressioLayout::Fortran, |shared| {
+            assert_eq!(shared.shape(), vec![4, 3, 2]);
+            assert_eq!(shared, &fortran);
+        });
+
+        let empty = PressioData::new_empty_with_layout(
+            PressioDtype::F32,
+            [2, 3, 4],
+            PressioLayout::Fortran,
+        );
+        assert_eq!(empty.shape(), vec![4, 3, 2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn share_non_standard_layout() -> Result<(), PressioError> {
+        use ndarray::ShapeBuilder;
+
+        let values = (0..24).collect::<Vec<i32>>();
+        let fortran = ndarray::Array::from_shape_vec((2, 3, 4).f(), values.clone()).unwrap();
+
+        let ((), sharing) =
+            PressioData::new_with_shared_or_copied(&fortran, PressioLayout::C, |shared| {
+                assert_eq!(shared.shape(), vec![4, 3, 2]);
+                assert_eq!(
+                    shared.clone_into_array(),
+                    Some(PressioArray::I32(fortran.t().to_owned().into_dyn()))
+                );
+            });
+        assert_eq!(sharing, SharedOrCopied::SharedReversed);
+
+        PressioData::try_new_with_shared(&fortran, PressioLayout::Fortran, |shared| {
+            assert_eq!(shared.shape(), vec![2, 3, 4]);
+            assert_eq!(
+                shared,
+                &PressioData::new_copied_with_layout(
+                    ndarray::Array::from_shape_vec((4, 3, 2), values).unwrap(),
+                    PressioLayout::Fortran
+                )
+            );
+        })?;
+
+        let strided = fortran.slice(ndarray::s![.., ..;2, ..]);
+        let ((), sharing) =
+            PressioData::new_with_shared_or_copied(&strided, PressioLayout::C, |shared| {
+                assert_eq!(shared, &PressioData::new_copied(strided.to_owned()));
+            });
+        assert_eq!(sharing, SharedOrCopied::Copied);
+        assert!(PressioData::try_new_with_shared(&strided, PressioLayout::C, |_| ()).is_err());
+
+        let bytes = ndarray::Array::from_shape_vec((2, 3).f(), vec![0_u8, 1, 2, 3, 4, 5]).unwrap();
+        let ((), sharing) =
+            PressioData::new_with_bytes_shared_or_copied(&bytes, PressioLayout::C, |shared| {
+                assert_eq!(shared.dtype(), Some(PressioDtype::Byte));
+                assert_eq!(shared.shape(), vec![3, 2]);
+            });
+        assert_eq!(sharing, SharedOrCopied::SharedReversed);
+        let ((), sharing) = PressioData::new_with_bytes_shared_or_copied(
+            bytes.slice(ndarray::s![.., ..;2]),
+            PressioLayout::C,
+            |_| (),
+        );
+        assert_eq!(sharing, SharedOrCopied::Copied);
+        PressioData::try_new_with_bytes_shared(&bytes, PressioLayout::Fortran, |shared| {
+            assert_eq!(shared.shape(), vec![2, 3]);
+            assert_eq!(
+                shared,
+                &PressioData::new_bytes_copied_with_layout(bytes.t(), PressioLayout::Fortran)
+            );
+        })?;
+        assert!(
+            PressioData::try_new_with_bytes_shared(
+                bytes.slice(ndarray::s![.., ..;2]),
+                PressioLayout::C,
+                |_| ()
+            )
+            .is_err()
+        );
+
+        // copies of non-standard layout arrays are always in standard layout
+        assert_eq!(
+            PressioData::new_copied(&fortran).clone_into_array(),
+            Some(PressioArray::I32(fortran.into_dyn()))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn select_slice_transpose() -> Result<(), PressioError> {
+        let array =
+            ndarray::Array::from_shape_fn((4, 6, 5), |(i, j, k)| (i * 100 + j * 10 + k) as u16)
+                .into_dyn();
+        let data = PressioData::new_copied(&array);
+
+        let selected = data.select(&[1, 0, 4], &[2, 3, 1], &[2, 2, 1], &[1, 2, 1])?;
+        assert_eq!(
+            selected.clone_into_array(),
+            Some(PressioArray::U16(
+                array
+                    .select(ndarray::Axis(0), &[1, 3])
+                    .select(ndarray::Axis(1), &[0, 1, 3, 4])
+                    .select(ndarray::Axis(2), &[4])
+            ))
+        );
+        assert!(
+            data.select(&[0, 0, 0], &[1, 1, 1], &[1, 1, 6], &[1, 1, 1])
+                .is_err()
+        );
+        assert!(
+            data.select(&[0, 0, 0], &[1, 1, 1], &[1, 2, 1], &[1, 2, 1])
+                .is_err()
+        );
+        assert!(data.select(&[0, 0], &[1, 1], &[1, 1], &[1, 1]).is_err());
+
+        let info = ndarray::s![-3.., ..;-2, ndarray::NewAxis, 3];
+        assert_eq!(
+            data.slice(info)?.clone_into_array(),
+            Some(PressioArray::U16(array.slice(info).to_owned().into_dyn()))
+        );
+        assert!(data.slice(ndarray::s![.., .., 5]).is_err());
+        assert!(data.slice(ndarray::s![.., ..]).is_err());
+
+        assert_eq!(
+            data.transpose(&[2, 0, 1])?.clone_into_array(),
+            Some(PressioArray::U16(
+                array.view().permuted_axes(IxDyn(&[2, 0, 1])).to_owned()
+            ))
+        );
+        assert!(data.transpose(&[0, 0, 1]).is_err());
+        assert!(data.transpose(&[0, 1]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn range_checked_cast() -> Result<(), PressioError> {
+        let data = PressioData::new_copied(ndarray::array![-1.5, 0.5, 2.5, 300.0, f64::NAN]);
+
+        let (cast, report) = data.try_cast(PressioDtype::U8, CastPolicy::default())?;
+        assert_eq!(
+            cast.clone_into_array(),
+            Some(PressioArray::U8(
+                ndarray::array![0, 0, 2, 255, 0].into_dyn()
+            ))
+        );
+        assert_eq!(
+            report,
+            CastReport {
+                num_elements: 5,
+                num_clamped: 2,
+                num_nan: 1,
+                num_rounded: 3,
+            }
+        );
+
+        let nearest = CastPolicy {
+            overflow: CastOverflow::Saturate,
+            rounding: CastRounding::Nearest,
+        };
+        let (cast, _) = data.try_cast(PressioDtype::I8, nearest)?;
+        assert_eq!(
+            cast.clone_into_array(),
+            Some(PressioArray::I8(
+                ndarray::array![-2, 0, 2, 127, 0].into_dyn()
+            ))
+        );
+
+        let strict = CastPolicy {
+            overflow: CastOverflow::Error,
+            rounding: CastRounding::Floor,
+        };
+        assert!(data.try_cast(PressioDtype::I16, strict).is_err());
+
+        let ints = PressioData::new_copied(ndarray::array![-40_000_i64, 7, 40_000]);
+        assert!(ints.try_cast(PressioDtype::I16, strict).is_err());
+        let (cast, report) = ints.try_cast(PressioDtype::I32, strict)?;
+        assert!(report.is_exact());
+        assert_eq!(
+            cast.clone_into_array(),
+            Some(PressioArray::I32(
+                ndarray::array![-40_000, 7, 40_000].into_dyn()
+            ))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn numeric_visitor() {
+        struct Sum;
+
+        impl NumericVisitor for Sum {
+            type Output = f64;
+
+            fn visit<T: PressioNumeric>(self, array: ndarray::ArrayViewD<'_, T>) -> f64 {
+                array.iter().map(|&x| x.to_f64()).sum()
+            }
+        }
+
+        let arrays = [
+            PressioArray::Byte(ndarray::array![1_u8, 2, 3].into_dyn()),
+            PressioArray::U64(ndarray::array![1_u64, 2, 3].into_dyn()),
+            PressioArray::I16(ndarray::array![-1_i16, 4, 3].into_dyn()),
+            PressioArray::F32(ndarray::array![0.5_f32, 1.5, 4.0].into_dyn()),
+        ];
+        for array in &arrays {
+            assert_eq!(array.map_numeric(Sum), Some(6.0));
+        }
+        assert_eq!(
+            PressioArray::Bool(ndarray::array![true].into_dyn()).map_numeric(Sum),
+            None
+        );
+
+        assert_eq!(
+            arrays[0].as_array::<u8>(),
+            Some(&ndarray::array![1_u8, 2, 3].into_dyn())
+        );
+        assert_eq!(arrays[1].as_array::<i64>(), None);
+        assert_eq!(
+            arrays[2].clone().into_array::<i16>(),
+            Ok(ndarray::array![-1_i16, 4, 3].into_dyn())
+        );
+        assert_eq!(
+            arrays[3].clone().into_array::<f64>(),
+            Err(arrays[3].clone())
+        );
+    }
+
+    #[test]
+    fn data_stats() -> Result<(), PressioError> {
+        let data = PressioData::new_copied(ndarray::array![
+            [0.0, 2.0, f64::NAN],
+            [4.0, f64::INFINITY, 0.0]
+        ]);
+        let stats = data.stats()?;
+        assert_eq!(stats.num_elements, 6);
+        assert_eq!(
+            (stats.num_nan, stats.num_inf, stats.num_finite()),
+            (1, 1, 4)
+        );
+        assert_eq!((stats.min, stats.max, stats.range), (0.0, 4.0, 4.0));
+        assert_eq!(stats.mean, 1.5);
+        assert!((stats.variance - 2.75).abs() < 1e-12);
+        assert_eq!(stats.zero_fraction, 2.0 / 6.0);
+
+        let stats = PressioData::new_copied(ndarray::array![-3_i16, 7, 1]).stats()?;
+        assert_eq!((stats.min, stats.max, stats.mean), (-3.0, 7.0, 5.0 / 3.0));
+        assert_eq!(stats.zero_fraction, 0.0);
+
+        let stats = PressioData::new_bytes_copied(ndarray::array![5_u8, 5]).stats()?;
+        assert_eq!((stats.range, stats.variance), (0.0, 0.0));
+
+        let stats = PressioData::new_copied(ndarray::array![f32::NAN]).stats()?;
+        assert!(stats.min.is_nan() && stats.mean.is_nan());
+
+        assert!(
+            PressioData::new_copied(ndarray::array![true])
+                .stats()
+                .is_err()
+        );
+        assert!(
+            PressioData::new_empty(PressioDtype::F32, [3])
+                .stats()
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn error_against_reference() -> Result<(), PressioError> {
+        let reference = PressioData::new_copied(ndarray::array![[0_i32, 2, 4], [6, 8, 10]]);
+        let data = PressioData::new_copied(ndarray::array![[0.5_f32, 2.0, 3.0], [6.0, 8.5, 10.0]]);
+
+        let stats = data.error_against(&reference)?;
+        assert_eq!(
+            stats.difference.clone_into_array(),
+            Some(PressioArray::F64(
+                ndarray::array![[0.5, 0.0, -1.0], [0.0, 0.5, 0.0]].into_dyn()
+            ))
+        );
+
+        let summary = stats.summary;
+        assert_eq!((summary.num_elements, summary.num_non_finite), (6, 0));
+        assert_eq!((summary.min_error, summary.max_error), (-1.0, 0.5));
+        assert_eq!(summary.max_abs_error, 1.0);
+        assert_eq!(summary.value_range, 10.0);
+        assert_eq!(summary.max_rel_error, 0.1);
+        assert_eq!(summary.mse, 0.25);
+        assert_eq!(summary.rmse, 0.5);
+        assert!((summary.psnr - 20.0 * 20.0_f64.log10()).abs() < 1e-12);
+
+        assert_eq!(stats.histogram.counts.iter().sum::<usize>(), 6);
+        assert_eq!(stats.histogram.counts.first(), Some(&1));
+        assert_eq!(stats.histogram.counts.last(), Some(&2));
+        assert_eq!(stats.autocorrelation.len(), 5);
+        assert!(stats.autocorrelation.iter().all(|r| r.abs() <= 1.0));
+
+        let exact = reference.error_against(&reference)?;
+        assert_eq!(exact.summary.max_abs_error, 0.0);
+        assert_eq!(exact.summary.psnr, f64::INFINITY);
+        assert!(exact.autocorrelation.iter().all(|&r| r == 0.0));
+
+        let constant = PressioData::new_copied(ndarray::array![2.0, 2.0, 2.0]);
+        let exact = constant.error_against(&constant)?;
+        assert_eq!(exact.summary.value_range, 0.0);
+        assert_eq!(exact.summary.max_rel_error, 0.0);
+        assert_eq!(exact.summary.psnr, f64::INFINITY);
+        let inexact = PressioData::new_copied(ndarray::array![2.0, 2.5, 2.0])
+            .error_against(&constant)?
+            .summary;
+        assert_eq!(inexact.max_rel_error, f64::INFINITY);
+        assert_eq!(inexact.psnr, f64::NEG_INFINITY);
+
+        let nan = PressioData::new_copied(ndarray::array![1.0, f64::NAN]);
+        let stats = nan.error_against(&PressioData::new_copied(ndarray::array![1.0, 2.0]))?;
+        assert_eq!(stats.summary.num_non_finite, 1);
+        assert_eq!(stats.summary.max_abs_error, 0.0);
+
+        assert!(data.error_against(&nan).is_err());
+        assert!(
+            PressioData::new_copied(ndarray::array![true])
+                .error_against(&PressioData::new_copied(ndarray::array![1_u8]))
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "half")]
+    fn half_precision() -> Result<(), PressioError> {
+        use half::{bf16, f16};
+
+        let array = ndarray::Array::from_shape_fn((2, 3), |(i, j)| {
+            f16::from_f32(i as f32 * 0.5 - j as f32 * 0.25)
+        })
+        .into_dyn();
+
+        let data = PressioData::new_copied_half(&array);
+        assert_eq!(data.precision(), HalfPrecision::F16);
+        assert_eq!(data.dtype(), Some(PressioDtype::F32));
+        assert_eq!(data.shape(), vec![2, 3]);
+        assert_eq!(
+            data.clone_into_original_array(),
+            HalfArray::F16(array.clone())
+        );
+
+        let (decompressed, _) = data.try_cast(PressioDtype::F64, CastPolicy::default())?;
+        assert_eq!(
+            data.precision().narrow(&decompressed),
+            Some(HalfArray::F16(array))
+        );
+
+        let array = ndarray::array![bf16::from_f32(1.0e30), bf16::from_f32(-3.0)];
+        let data = PressioData::new_copied_half(&array);
+        assert_eq!(data.precision(), HalfPrecision::BF16);
+        assert_eq!(data.clone_into_half_array::<bf16>(), Some(array.into_dyn()));
+        assert!(
+            PressioData::new_copied(ndarray::array![1_u8])
+                .clone_into_half_array::<f16>()
+                .is_none()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "complex")]
+    fn complex_arrays() {
+        use num_complex::Complex;
+
+        let array = ndarray::Array::from_shape_fn((2, 3), |(i, j)| {
+            Complex::new(i as f64, -(j as f64) * 0.5)
+        })
+        .into_dyn();
+
+        let data = PressioData::new_copied_complex(&array);
+        assert_eq!(data.dtype(), Some(PressioDtype::F64));
+        assert_eq!(data.shape(), vec![2, 3, 2]);
+        assert_eq!(
+            data.clone_into_array(),
+            Some(PressioArray::F64(
+                ndarray::Array::from_shape_fn((2, 3, 2), |(i, j, k)| {
+                    if k == 0 { i as f64 } else { -(j as f64) * 0.5 }
+                })
+                .into_dyn()
+            ))
+        );
+        assert_eq!(data.clone_into_complex_array(), Some(array.clone()));
+        assert!(data.clone_into_complex_array::<f32>().is_none());
+
+        PressioData::new_with_shared_complex(array.t(), |shared| {
+            assert_eq!(shared.shape(), vec![3, 2, 2]);
+            assert_eq!(
+                shared.clone_into_complex_array(),
+                Some(array.t().to_owned())
+            );
+        });
+
+        let real = PressioData::new_copied(ndarray::array![[1.0_f32, 2.0, 3.0]]);
+        assert!(real.clone_into_complex_array::<f32>().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "mmap")]
+    fn mapped_data() -> Result<(), PressioError> {
+        let path = std::env::temp_dir().join(format!("libpressio-{}.f32", std::process::id()));
+
+        let data = PressioData::new_copied(input_data());
+        data.write_mapped(&path)?;
+        assert_eq!(
+            std::fs::metadata(&path).map(|metadata| metadata.len()).ok(),
+            Some(30 * 30 * 4)
+        );
+
+        let mapped = unsafe { MappedPressioData::map(&path, PressioDtype::F32, [30, 30]) }?;
+        assert_eq!(*mapped, data);
+        assert!(unsafe { MappedPressioData::map(&path, PressioDtype::F64, [30, 30]) }.is_err());
+        assert!(
+            unsafe { MappedPressioData::map(&path, PressioDtype::F32, [usize::MAX, 2]) }.is_err()
+        );
+
+        let mut lib = Pressio::new()?;
+        let mut compressor = lib.get_compressor("noop")?;
+        let report = verify_roundtrip(&mut compressor, &mapped, ErrorBound::Lossless)?;
+        assert!(report.is_ok());
+
+        std::mem::drop(mapped);
+        let _ = std::fs::remove_file(&path);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "arrow")]
+    fn arrow_conversions() -> Result<(), PressioError> {
+        use arrow_array::{Array, Float64Array};
+
+        let values = Float64Array::from(vec![1.0, 2.0, 3.0, 4.0]);
+        let data = PressioData::from_arrow(&values)?;
+        assert_eq!(data.shape(), vec![4]);
+        assert_eq!(
+            data.as_bytes().map(<[u8]>::as_ptr),
+            Some(values.values().inner().as_ptr())
+        );
+
+        let sliced = PressioData::from_arrow(&values.slice(1, 2))?;
+        assert_eq!(
+            sliced.clone_into_array(),
+            Some(PressioArray::F64(ndarray::array![2.0, 3.0].into_dyn()))
+        );
+
+        let array =
+            ndarray::Array::from_shape_fn((2, 3, 4), |(i, j, k)| (i * 100 + j * 10 + k) as i32);
+        let data = PressioData::new_copied(&array);
+        let tensor = data.clone().into_arrow_tensor()?;
+        assert_eq!(tensor.len(), 2);
+        assert_eq!(tensor.value_length(), 12);
+
+        let roundtrip = PressioData::from_arrow_tensor(&tensor, &[3, 4])?;
+        assert_eq!(*roundtrip, data);
+        assert!(PressioData::from_arrow_tensor(&tensor, &[5, 4]).is_err());
+
+        let flat = PressioData::new_copied(ndarray::array![true, false, true]).into_arrow()?;
+        assert_eq!(flat.len(), 3);
+        assert_eq!(
+            PressioData::from_arrow(flat.as_ref())?.clone_into_array(),
+            Some(PressioArray::Bool(
+                ndarray::array![true, false, true].into_dyn()
+            ))
+        );
+
+        Ok(())
+    }
+
     // #[test]
     // fn unsafe_works() {
     //     use std::ptr;
@@ -0,0 +1,238 @@
+use ndarray::ArrayViewD;
+
+use crate::{
+    NumericVisitor, PressioCompressor, PressioData, PressioDtype, PressioError, PressioNumeric,
+};
+
+/// Which values are masked out by [`PressioCompressor::compress_masked`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MaskPolicy {
+    /// Mask out NaN values.
+    pub nan: bool,
+    /// Mask out positive and negative infinity.
+    pub inf: bool,
+    /// Mask out a sentinel fill value, which is compared after converting
+    /// each element to [`f64`].
+    pub fill_value: Option<f64>,
+}
+
+impl Default for MaskPolicy {
+    fn default() -> Self {
+        Self {
+            nan: true,
+            inf: true,
+            fill_value: None,
+        }
+    }
+}
+
+/// Data that was compressed by [`PressioCompressor::compress_masked`].
+#[derive(Clone, Debug)]
+pub struct MaskedCompressed {
+    /// The compressed field, in which the masked elements were replaced by
+    /// the mean of the finite unmasked elements.
+    pub field: PressioData,
+    /// The losslessly compressed validity mask, together with the original
+    /// values of the masked elements, or [`None`] if no elements were
+    /// masked.
+    pub mask: Option<PressioData>,
+    pub num_masked: usize,
+}
+
+impl MaskedCompressed {
+    /// The total number of compressed bytes.
+    pub fn num_bytes(&self) -> usize {
+        self.field.num_bytes() + self.mask.as_ref().map_or(0, PressioData::num_bytes)
+    }
+}
+
+impl PressioCompressor {
+    /// Compress the `input` after masking out the values selected by the
+    /// `policy`, which many lossy compressors do not handle well.
+    ///
+    /// The masked elements are replaced by the mean of the finite unmasked
+    /// elements, or by zero if there are none, before the field is compressed
+    /// with this compressor. The input is only copied if any elements are
+    /// masked. The validity mask and the original values of the masked
+    /// elements are compressed with the `mask_compressor`, which must be
+    /// lossless, such that [`PressioCompressor::decompress_masked`] restores
+    /// them exactly.
+    ///
+    /// An error is returned if the `input` has no numeric values or if
+    /// either compressor fails.
+    pub fn compress_masked(
+        &mut self,
+        input: &PressioData,
+        policy: MaskPolicy,
+        mask_compressor: &mut PressioCompressor,
+    ) -> Result<MaskedCompressed, PressioError> {
+        let (Some(dtype), Some(bytes)) = (input.dtype(), input.as_bytes()) else {
+            return Err(masked_error("cannot compress data without values"));
+        };
+        let Some((mask, mean)) = input.map_numeric(MaskVisitor { policy }) else {
+            return Err(masked_error("cannot mask data without numeric values"));
+        };
+
+        let num_masked = mask.iter().filter(|&&masked| masked).count();
+
+        if num_masked == 0 {
+            return Ok(MaskedCompressed {
+                field: self.compress(input, PressioData::new_empty(PressioDtype::Byte, []))?,
+                mask: None,
+                num_masked,
+            });
+        }
+
+        let Some(filled) = input.map_numeric(FillVisitor { mask: &mask, mean }) else {
+            unreachable!("the data has already been masked")
+        };
+        let compressed_field =
+            self.compress(&filled, PressioData::new_empty(PressioDtype::Byte, []))?;
+
+        // the mask is packed into bits, followed by the masked values
+        let size = dtype.size_in_bytes();
+        let mut payload = vec![0_u8; mask.len().div_ceil(8)];
+        payload.reserve(num_masked * size);
+        for (i, _) in mask.iter().enumerate().filter(|(_, masked)| **masked) {
+            payload[i / 8] |= 1 << (i % 8);
+        }
+        for (i, _) in mask.iter().enumerate().filter(|(_, masked)| **masked) {
+            payload.extend_from_slice(&bytes[i * size..(i + 1) * size]);
+        }
+
+        let payload = PressioData::from_bytes(PressioDtype::Byte, &[payload.len()], &payload);
+        let compressed_mask =
+            mask_compressor.compress(&payload, PressioData::new_empty(PressioDtype::Byte, []))?;
+
+        Ok(MaskedCompressed {
+            field: compressed_field,
+            mask: Some(compressed_mask),
+            num_masked,
+        })
+    }
+
+    /// Decompress data that was compressed with
+    /// [`PressioCompressor::compress_masked`] into the `output`, which must
+    /// have the dtype and shape of the original data, and restore the masked
+    /// elements exactly.
+    ///
+    /// The same `mask_compressor` must be used as for compression.
+    pub fn decompress_masked(
+        &mut self,
+        compressed: &MaskedCompressed,
+        output: PressioData,
+        mask_compressor: &mut PressioCompressor,
+    ) -> Result<PressioData, PressioError> {
+        let decompressed = self.decompress(&compressed.field, output)?;
+
+        let Some(compressed_mask) = &compressed.mask else {
+            return Ok(decompressed);
+        };
+
+        let payload = mask_compressor.decompress(
+            compressed_mask,
+            PressioData::new_empty(PressioDtype::Byte, []),
+        )?;
+        let payload = payload.as_bytes().unwrap_or_default();
+
+        let (Some(dtype), Some(bytes)) = (decompressed.dtype(), decompressed.as_bytes()) else {
+            return Err(masked_error("decompressed data has no values"));
+        };
+        let size = dtype.size_in_bytes();
+        let num_elements = decompressed.len();
+        let (bits, values) = payload.split_at(num_elements.div_ceil(8).min(payload.len()));
+
+        if values.len() != compressed.num_masked * size {
+            return Err(masked_error(&format!(
+                "mask for {} masked {dtype} elements has {} value bytes",
+                compressed.num_masked,
+                values.len(),
+            )));
+        }
+
+        let mut restored = bytes.to_vec();
+        let masked = (0..num_elements).filter(|i| bits[i / 8] & (1 << (i % 8)) != 0);
+        for (i, value) in masked.zip(values.chunks_exact(size)) {
+            restored[i * size..(i + 1) * size].copy_from_slice(value);
+        }
+
+        Ok(PressioData::from_bytes(
+            dtype,
+            &decompressed.shape(),
+            &restored,
+        ))
+    }
+}
+
+/// Computes the validity mask and the mean of the finite unmasked elements,
+/// which is [`None`] if there are none.
+struct MaskVisitor {
+    policy: MaskPolicy,
+}
+
+impl NumericVisitor for MaskVisitor {
+    type Output = (Vec<bool>, Option<f64>);
+
+    fn visit<T: PressioNumeric>(self, array: ArrayViewD<'_, T>) -> Self::Output {
+        let MaskPolicy {
+            nan,
+            inf,
+            fill_value,
+        } = self.policy;
+
+        let mut sum = 0.0;
+        let mut num_finite = 0_usize;
+        let mask = array
+            .iter()
+            .map(|&x| {
+                let x = x.to_f64();
+                let masked = (nan && x.is_nan())
+                    || (inf && x.is_infinite())
+                    || fill_value.is_some_and(|fill| x == fill);
+                // unmasked NaN and Inf values must not leak into the mean
+                if !masked && x.is_finite() {
+                    sum += x;
+                    num_finite += 1;
+                }
+                masked
+            })
+            .collect::<Vec<_>>();
+
+        let mean = (num_finite > 0).then(|| sum / num_finite as f64);
+
+        (mask, mean)
+    }
+}
+
+/// Copies the data, with the masked elements replaced by the `mean`, or by
+/// zero if there is no mean.
+struct FillVisitor<'a> {
+    mask: &'a [bool],
+    mean: Option<f64>,
+}
+
+impl NumericVisitor for FillVisitor<'_> {
+    type Output = PressioData;
+
+    fn visit<T: PressioNumeric>(self, array: ArrayViewD<'_, T>) -> Self::Output {
+        let neutral = self.mean.map_or_else(T::default, T::from_f64);
+
+        let filled = array
+            .iter()
+            .zip(self.mask)
+            .map(|(&x, &masked)| if masked { neutral } else { x })
+            .collect::<Vec<_>>();
+        let Ok(filled) = ndarray::Array::from_shape_vec(array.raw_dim(), filled) else {
+            unreachable!("the filled data has the same number of elements")
+        };
+
+        PressioData::new_copied(filled)
+    }
+}
+
+fn masked_error(message: &str) -> PressioError {
+    PressioError {
+        error_code: 1,
+        message: String::from(message),
+    }
+}
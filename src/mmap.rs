@@ -0,0 +1,135 @@
+use std::{fs::File, ops::Deref, path::Path, ptr::NonNull};
+
+use memmap2::{Mmap, MmapMut};
+
+use crate::{PressioData, PressioDtype, PressioError};
+
+/// Read-only [`PressioData`] that is backed by a memory-mapped raw binary
+/// file instead of heap memory.
+///
+/// The mapped data dereferences to a non-owning [`PressioData`], which can
+/// e.g. be compressed directly.
+pub struct MappedPressioData {
+    // the data must be dropped before the mapping that it points into
+    data: PressioData,
+    _mmap: Mmap,
+}
+
+impl MappedPressioData {
+    /// Memory-map the raw binary file at `path`, which contains elements of
+    /// the `dtype` in native endianness and C order.
+    ///
+    /// An error is returned if the file cannot be mapped or if its size does
+    /// not match the `dtype` and `shape`.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, e.g. by another process,
+    /// while it is mapped.
+    pub unsafe fn map<D: AsRef<[usize]>>(
+        path: impl AsRef<Path>,
+        dtype: PressioDtype,
+        shape: D,
+    ) -> Result<Self, PressioError> {
+        let path = path.as_ref();
+        let shape = shape.as_ref();
+
+        let file = File::open(path).map_err(|err| {
+            PressioError::io_error(&err, &format!("open raw file {}", path.display()))
+        })?;
+        // Safety: the caller guarantees that the file is not modified
+        let mmap = unsafe { Mmap::map(&file) }.map_err(|err| {
+            PressioError::io_error(&err, &format!("map raw file {}", path.display()))
+        })?;
+
+        let expected = shape
+            .iter()
+            .try_fold(dtype.size_in_bytes(), |acc, &len| acc.checked_mul(len))
+            .ok_or_else(|| PressioError {
+                error_code: 1,
+                message: format!("{dtype} data with shape {shape:?} is too large to map"),
+            })?;
+        if mmap.len() != expected {
+            return Err(PressioError {
+                error_code: 1,
+                message: format!(
+                    "raw file {} has {} bytes but {dtype} data with shape {shape:?} requires {expected} bytes",
+                    path.display(),
+                    mmap.len(),
+                ),
+            });
+        }
+
+        let data = unsafe {
+            libpressio_sys::pressio_data_new_nonowning(
+                dtype.into_raw(),
+                // SAFETY: we only give access to &PressioData, which does
+                //         not expose mutating access, so we can cast a
+                //         const ptr to a mut ptr here
+                mmap.as_ptr().cast_mut().cast(),
+                shape.len(),
+                shape.as_ptr(),
+            )
+        };
+        let data = NonNull::new(data).expect("pressio_data_new_nonowning must not return null");
+
+        Ok(Self {
+            data: PressioData { data },
+            _mmap: mmap,
+        })
+    }
+}
+
+impl Deref for MappedPressioData {
+    type Target = PressioData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl PressioData {
+    /// Write the raw bytes of the data, in native endianness and C order, to
+    /// the file at `path` through a memory mapping.
+    ///
+    /// The file is created or truncated, and can be mapped again using
+    /// [`MappedPressioData::map`]. An error is returned if the data has no
+    /// values.
+    pub fn write_mapped(&self, path: impl AsRef<Path>) -> Result<(), PressioError> {
+        let path = path.as_ref();
+
+        let Some(bytes) = self.as_bytes() else {
+            return Err(PressioError {
+                error_code: 1,
+                message: String::from("cannot write data without values"),
+            });
+        };
+
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)
+            .map_err(|err| {
+                PressioError::io_error(&err, &format!("create raw file {}", path.display()))
+            })?;
+        file.set_len(bytes.len() as u64).map_err(|err| {
+            PressioError::io_error(&err, &format!("resize raw file {}", path.display()))
+        })?;
+
+        if bytes.is_empty() {
+            return Ok(());
+        }
+
+        // Safety: the file was just created by us and is only modified
+        //         through this mapping
+        let mut mmap = unsafe { MmapMut::map_mut(&file) }.map_err(|err| {
+            PressioError::io_error(&err, &format!("map raw file {}", path.display()))
+        })?;
+        mmap.copy_from_slice(bytes);
+        mmap.flush().map_err(|err| {
+            PressioError::io_error(&err, &format!("write raw file {}", path.display()))
+        })
+    }
+}
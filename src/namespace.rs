@@ -0,0 +1,130 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{PressioError, PressioOption, PressioOptions};
+
+impl PressioOptions {
+    /// List the distinct top-level namespaces of all keys, in sorted order.
+    ///
+    /// For example, options with the keys `sz:abs_err_bound` and
+    /// `pressio:metric` have the namespaces `pressio` and `sz`. Keys without
+    /// a `:` separator have no namespace.
+    pub fn namespaces(&self) -> Vec<String> {
+        self.iter()
+            .filter_map(|(key, _)| Some(String::from(key?.split_once(':')?.0)))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Extract all options whose key is inside the `prefix` namespace.
+    ///
+    /// The `prefix:` is stripped from the keys of the returned options, e.g.
+    /// `sz:abs_err_bound` becomes `abs_err_bound` for the prefix `sz`. The
+    /// prefix may itself be nested, e.g. `pressio:sz`.
+    pub fn subtree(&self, prefix: impl AsRef<str>) -> Result<Self, PressioError> {
+        let prefix = prefix.as_ref().trim_end_matches(':');
+
+        let mut subtree = Self::new()?;
+        for (key, option) in self.iter() {
+            let (Some(key), Some(option)) = (key, option) else {
+                continue;
+            };
+            if let Some(key) = key
+                .strip_prefix(prefix)
+                .and_then(|key| key.strip_prefix(':'))
+            {
+                subtree.set(key, option)?;
+            }
+        }
+        Ok(subtree)
+    }
+
+    /// Re-root all options under the `prefix` namespace.
+    ///
+    /// Every key `key` becomes `prefix:key`. Combined with
+    /// [`PressioOptions::subtree`], this moves options from one namespace into
+    /// another, e.g. to configure the child of a meta-compressor.
+    pub fn with_prefix(&self, prefix: impl AsRef<str>) -> Result<Self, PressioError> {
+        let prefix = prefix.as_ref().trim_end_matches(':');
+
+        let mut rerooted = Self::new()?;
+        for (key, option) in self.iter() {
+            let (Some(key), Some(option)) = (key, option) else {
+                continue;
+            };
+            if prefix.is_empty() {
+                rerooted.set(key, option)?;
+            } else {
+                rerooted.set(format!("{prefix}:{key}"), option)?;
+            }
+        }
+        Ok(rerooted)
+    }
+
+    /// Build a tree of the options, split at the `:` namespace separators.
+    pub fn tree(&self) -> PressioOptionsTree {
+        let mut root = PressioOptionsTree::default();
+        for (key, option) in self.iter() {
+            let (Some(key), Some(option)) = (key, option) else {
+                continue;
+            };
+            let node = key.split(':').fold(&mut root, |node, segment| {
+                node.children.entry(String::from(segment)).or_default()
+            });
+            node.value = Some(option);
+        }
+        root
+    }
+}
+
+/// A node in the namespace tree of [`PressioOptions`].
+///
+/// A node can both hold a value and have children, e.g. for options with the
+/// keys `pressio` and `pressio:metric`.
+#[derive(Clone, Debug, Default)]
+pub struct PressioOptionsTree {
+    pub value: Option<PressioOption>,
+    pub children: BTreeMap<String, PressioOptionsTree>,
+}
+
+impl PressioOptionsTree {
+    /// Look up the node at the `:`-separated `path`, relative to this node.
+    pub fn get(&self, path: impl AsRef<str>) -> Option<&Self> {
+        let path = path.as_ref();
+        if path.is_empty() {
+            return Some(self);
+        }
+        path.split(':')
+            .try_fold(self, |node, segment| node.children.get(segment))
+    }
+
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Iterate depth-first over all nodes below this node that hold a
+    /// value, yielding their full `:`-separated key and depth.
+    pub fn walk(&self) -> impl Iterator<Item = (String, usize, &PressioOption)> + '_ {
+        let mut stack = self
+            .children
+            .iter()
+            .rev()
+            .map(|(segment, node)| (segment.clone(), 0, node))
+            .collect::<Vec<_>>();
+
+        std::iter::from_fn(move || {
+            loop {
+                let (key, depth, node) = stack.pop()?;
+                stack.extend(
+                    node.children
+                        .iter()
+                        .rev()
+                        .map(|(segment, child)| (format!("{key}:{segment}"), depth + 1, child)),
+                );
+                if let Some(value) = &node.value {
+                    return Some((key, depth, value));
+                }
+            }
+        })
+    }
+}
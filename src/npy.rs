@@ -0,0 +1,402 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use crate::{PressioData, PressioDtype, PressioError};
+
+const NPY_MAGIC: &[u8; 6] = b"\x93NUMPY";
+
+impl PressioData {
+    /// Read data from an `.npy` file.
+    ///
+    /// Big- and little-endian data is converted to native endianness, and
+    /// Fortran-ordered data is transposed into C order such that the shape
+    /// of the array is preserved. Zero-dimensional arrays are read with the
+    /// shape `[1]`. Bool arrays with values other than 0 and 1 are rejected.
+    pub fn read_npy(path: impl AsRef<Path>) -> Result<Self, PressioError> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|err| {
+            PressioError::io_error(&err, &format!("open npy file {}", path.display()))
+        })?;
+        Self::read_npy_from(BufReader::new(file))
+    }
+
+    /// Read data in the `.npy` format from the `reader`.
+    ///
+    /// See [`PressioData::read_npy`] for details.
+    pub fn read_npy_from(mut reader: impl Read) -> Result<Self, PressioError> {
+        let mut preamble = [0_u8; 8];
+        reader
+            .read_exact(&mut preamble)
+            .map_err(|err| PressioError::io_error(&err, "read npy magic"))?;
+        if &preamble[..6] != NPY_MAGIC {
+            return Err(npy_error("missing npy magic string"));
+        }
+
+        let header_len = match preamble[6] {
+            1 => {
+                let mut len = [0_u8; 2];
+                reader
+                    .read_exact(&mut len)
+                    .map_err(|err| PressioError::io_error(&err, "read npy header length"))?;
+                usize::from(u16::from_le_bytes(len))
+            }
+            2 | 3 => {
+                let mut len = [0_u8; 4];
+                reader
+                    .read_exact(&mut len)
+                    .map_err(|err| PressioError::io_error(&err, "read npy header length"))?;
+                usize::try_from(u32::from_le_bytes(len))
+                    .map_err(|_| npy_error("npy header is too long"))?
+            }
+            major => {
+                return Err(npy_error(&format!("unsupported npy version {major}")));
+            }
+        };
+
+        let mut header = vec![0_u8; header_len];
+        reader
+            .read_exact(&mut header)
+            .map_err(|err| PressioError::io_error(&err, "read npy header"))?;
+        let header = std::str::from_utf8(&header)
+            .map_err(|err| PressioError::utf8_error(err, "npy header"))?;
+        let header = NpyHeader::parse(header)?;
+
+        let num_bytes = header
+            .shape
+            .iter()
+            .try_fold(header.dtype.size_in_bytes(), |acc, &len| {
+                acc.checked_mul(len)
+            })
+            .ok_or_else(|| npy_error("npy array is too large"))?;
+        // the header is untrusted, so only allocate as much as is actually read
+        let mut bytes = Vec::new();
+        reader
+            .take(num_bytes as u64)
+            .read_to_end(&mut bytes)
+            .map_err(|err| PressioError::io_error(&err, "read npy data"))?;
+        if bytes.len() < num_bytes {
+            return Err(npy_error("npy data is shorter than its header describes"));
+        }
+
+        // any byte other than 0 or 1 would be an invalid bool
+        if header.dtype == PressioDtype::Bool && bytes.iter().any(|&byte| byte > 1) {
+            return Err(npy_error(
+                "npy bool array contains values other than 0 and 1",
+            ));
+        }
+
+        let element_size = header.dtype.size_in_bytes();
+        if header.big_endian != cfg!(target_endian = "big") {
+            bytes
+                .chunks_exact_mut(element_size)
+                .for_each(<[u8]>::reverse);
+        }
+        if header.fortran_order {
+            bytes = fortran_to_c_order(&bytes, &header.shape, element_size);
+        }
+
+        Ok(Self::from_bytes(header.dtype, &header.shape, &bytes))
+    }
+
+    /// Write the data to an `.npy` file in C order and native endianness.
+    ///
+    /// [`PressioDtype::Byte`] data is written as `uint8`. An error is returned if the data has no values.
+    pub fn write_npy(&self, path: impl AsRef<Path>) -> Result<(), PressioError> {
+        let path = path.as_ref();
+        let file = File::create(path).map_err(|err| {
+            PressioError::io_error(&err, &format!("create npy file {}", path.display()))
+        })?;
+        let mut writer = BufWriter::new(file);
+        self.write_npy_to(&mut writer)?;
+        writer
+            .flush()
+            .map_err(|err| PressioError::io_error(&err, "write npy data"))
+    }
+
+    /// Write the data in the `.npy` format to the `writer`.
+    ///
+    /// See [`PressioData::write_npy`] for details.
+    pub fn write_npy_to(&self, mut writer: impl Write) -> Result<(), PressioError> {
+        let (Some(dtype), Some(bytes)) = (self.dtype(), self.as_bytes()) else {
+            return Err(npy_error("cannot write data without values to npy"));
+        };
+
+        let byte_order = match dtype.size_in_bytes() {
+            1 => '|',
+            _ if cfg!(target_endian = "big") => '>',
+            _ => '<',
+        };
+        let kind = match dtype {
+            PressioDtype::Bool => "b1",
+            PressioDtype::Byte | PressioDtype::U8 => "u1",
+            PressioDtype::U16 => "u2",
+            PressioDtype::U32 => "u4",
+            PressioDtype::U64 => "u8",
+            PressioDtype::I8 => "i1",
+            PressioDtype::I16 => "i2",
+            PressioDtype::I32 => "i4",
+            PressioDtype::I64 => "i8",
+            PressioDtype::F32 => "f4",
+            PressioDtype::F64 => "f8",
+        };
+
+        let shape = match self.shape().as_slice() {
+            // libpressio data without dimensions has no elements
+            [] => String::from("(0,)"),
+            [len] => format!("({len},)"),
+            shape => format!(
+                "({})",
+                shape
+                    .iter()
+                    .map(usize::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+
+        let mut header = format!(
+            "{{'descr': '{byte_order}{kind}', 'fortran_order': False, 'shape': {shape}, }}"
+        );
+
+        // the header is padded with spaces and terminated by a newline such
+        //  that the data starts at a multiple of 64 bytes
+        let padded_len = |len_size: usize| {
+            let preamble_len = NPY_MAGIC.len() + 2 + len_size;
+            (preamble_len + header.len() + 1).next_multiple_of(64) - preamble_len
+        };
+        let (version, len_size) = if padded_len(2) <= usize::from(u16::MAX) {
+            (1, 2)
+        } else {
+            (2, 4)
+        };
+        let padded_len = padded_len(len_size);
+        header.extend(std::iter::repeat_n(' ', padded_len - header.len() - 1));
+        header.push('\n');
+
+        let mut preamble = NPY_MAGIC.to_vec();
+        preamble.extend_from_slice(&[version, 0]);
+        if version == 1 {
+            let len =
+                u16::try_from(header.len()).map_err(|_| npy_error("npy header is too long"))?;
+            preamble.extend_from_slice(&len.to_le_bytes());
+        } else {
+            let len =
+                u32::try_from(header.len()).map_err(|_| npy_error("npy header is too long"))?;
+            preamble.extend_from_slice(&len.to_le_bytes());
+        }
+
+        writer
+            .write_all(&preamble)
+            .and_then(|()| writer.write_all(header.as_bytes()))
+            .and_then(|()| writer.write_all(bytes))
+            .map_err(|err| PressioError::io_error(&err, "write npy data"))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct NpyHeader {
+    pub(crate) dtype: PressioDtype,
+    pub(crate) big_endian: bool,
+    pub(crate) fortran_order: bool,
+    pub(crate) shape: Vec<usize>,
+}
+
+impl NpyHeader {
+    /// Parse the Python dictionary literal of an npy header, e.g.
+    /// `{'descr': '<f4', 'fortran_order': False, 'shape': (3, 4), }`.
+    pub(crate) fn parse(header: &str) -> Result<Self, PressioError> {
+        let descr = header_value(header, "descr")?;
+        let descr = match descr.chars().next() {
+            Some(quote @ ('\'' | '"')) => descr[1..]
+                .split_once(quote)
+                .map(|(descr, _)| descr)
+                .ok_or_else(|| npy_error("unterminated npy descr"))?,
+            _ => {
+                return Err(npy_error(
+                    "unsupported npy descr, only simple dtypes are supported",
+                ));
+            }
+        };
+
+        let (byte_order, kind) = match descr.split_at_checked(1) {
+            Some((order @ ("<" | ">" | "|" | "="), kind)) => (order, kind),
+            _ => ("=", descr),
+        };
+        let big_endian = match byte_order {
+            ">" => true,
+            "<" => false,
+            _ => cfg!(target_endian = "big"),
+        };
+        let dtype = match kind {
+            "b1" | "?" => PressioDtype::Bool,
+            "u1" | "B" => PressioDtype::U8,
+            "u2" => PressioDtype::U16,
+            "u4" => PressioDtype::U32,
+            "u8" => PressioDtype::U64,
+            "i1" | "b" => PressioDtype::I8,
+            "i2" => PressioDtype::I16,
+            "i4" => PressioDtype::I32,
+            "i8" => PressioDtype::I64,
+            "f4" => PressioDtype::F32,
+            "f8" => PressioDtype::F64,
+            _ => return Err(npy_error(&format!("unsupported npy dtype `{descr}`"))),
+        };
+
+        let fortran_order = header_value(header, "fortran_order")?;
+        let fortran_order = if fortran_order.starts_with("True") {
+            true
+        } else if fortran_order.starts_with("False") {
+            false
+        } else {
+            return Err(npy_error("invalid npy fortran_order"));
+        };
+
+        let shape = header_value(header, "shape")?;
+        let shape = shape
+            .strip_prefix('(')
+            .and_then(|shape| shape.split_once(')'))
+            .map(|(shape, _)| shape)
+            .ok_or_else(|| npy_error("invalid npy shape"))?;
+        let mut shape = shape
+            .split(',')
+            .map(str::trim)
+            .filter(|len| !len.is_empty())
+            // numpy may write lengths as Python longs, e.g. `3L`
+            .map(|len| len.trim_end_matches('L').parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| npy_error("invalid npy shape"))?;
+        if shape.is_empty() {
+            shape.push(1);
+        }
+
+        Ok(Self {
+            dtype,
+            big_endian,
+            fortran_order,
+            shape,
+        })
+    }
+}
+
+/// The text following the `key` and its colon in the npy header.
+fn header_value<'a>(header: &'a str, key: &str) -> Result<&'a str, PressioError> {
+    [format!("'{key}'"), format!("\"{key}\"")]
+        .iter()
+        .find_map(|quoted| header.split_once(quoted.as_str()))
+        .and_then(|(_, value)| value.trim_start().strip_prefix(':'))
+        .map(str::trim_start)
+        .ok_or_else(|| npy_error(&format!("npy header is missing the `{key}` entry")))
+}
+
+/// Transpose column-major `bytes` with the given `shape` into row-major order.
+pub(crate) fn fortran_to_c_order(bytes: &[u8], shape: &[usize], element_size: usize) -> Vec<u8> {
+    let mut strides = Vec::with_capacity(shape.len());
+    let mut stride = element_size;
+    for len in shape {
+        strides.push(stride);
+        stride *= len;
+    }
+
+    let mut transposed = Vec::with_capacity(bytes.len());
+    let mut index = vec![0_usize; shape.len()];
+    for _ in 0..bytes.len() / element_size {
+        let offset = index
+            .iter()
+            .zip(&strides)
+            .map(|(i, s)| i * s)
+            .sum::<usize>();
+        transposed.extend_from_slice(&bytes[offset..offset + element_size]);
+
+        // increment the row-major index, with the last axis varying fastest
+        for (i, len) in index.iter_mut().zip(shape).rev() {
+            *i += 1;
+            if *i < *len {
+                break;
+            }
+            *i = 0;
+        }
+    }
+    transposed
+}
+
+fn npy_error(message: &str) -> PressioError {
+    PressioError {
+        error_code: 1,
+        message: String::from(message),
+    }
+}
+
+/// Read all arrays from an `.npz` archive, as written by `numpy.savez` or
+/// `numpy.savez_compressed`.
+///
+/// The arrays are returned in archive order, named without the `.npy`
+/// extension. See [`PressioData::read_npy`] for how each array is read.
+#[cfg(feature = "npz")]
+pub fn read_npz(path: impl AsRef<Path>) -> Result<Vec<(String, PressioData)>, PressioError> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|err| {
+        PressioError::io_error(&err, &format!("open npz file {}", path.display()))
+    })?;
+    let mut archive = zip::ZipArchive::new(BufReader::new(file)).map_err(zip_error)?;
+
+    (0..archive.len())
+        .map(|i| {
+            let entry = archive.by_index(i).map_err(zip_error)?;
+            let name = entry.name();
+            let name = String::from(name.strip_suffix(".npy").unwrap_or(name));
+            Ok((name, PressioData::read_npy_from(entry)?))
+        })
+        .collect()
+}
+
+/// Write named arrays to an `.npz` archive, which can be read using
+/// `numpy.load`.
+///
+/// If `compressed` is enabled, the arrays are deflated as in
+/// `numpy.savez_compressed`. See [`PressioData::write_npy`] for how each
+/// array is written.
+#[cfg(feature = "npz")]
+pub fn write_npz<'a, K: AsRef<str>>(
+    path: impl AsRef<Path>,
+    arrays: impl IntoIterator<Item = (K, &'a PressioData)>,
+    compressed: bool,
+) -> Result<(), PressioError> {
+    let path = path.as_ref();
+    let file = File::create(path).map_err(|err| {
+        PressioError::io_error(&err, &format!("create npz file {}", path.display()))
+    })?;
+    let mut archive = zip::ZipWriter::new(BufWriter::new(file));
+
+    let method = if compressed {
+        zip::CompressionMethod::Deflated
+    } else {
+        zip::CompressionMethod::Stored
+    };
+
+    for (name, data) in arrays {
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(method)
+            .large_file(data.num_bytes() >= 0xFFFF_FFFF);
+        archive
+            .start_file(format!("{}.npy", name.as_ref()), options)
+            .map_err(zip_error)?;
+        data.write_npy_to(&mut archive)?;
+    }
+
+    archive
+        .finish()
+        .map_err(zip_error)?
+        .flush()
+        .map_err(|err| PressioError::io_error(&err, "write npz archive"))
+}
+
+#[cfg(feature = "npz")]
+fn zip_error(err: zip::result::ZipError) -> PressioError {
+    PressioError {
+        error_code: 1,
+        message: format!("invalid npz archive: {err}"),
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{PressioCompressor, PressioError, PressioOption, PressioOptions};
+
+/// Whether an option can be set on a compressor or only describes it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PressioOptionKind {
+    /// Read-only information from [`PressioCompressor::get_configuration`].
+    Configuration,
+    /// Settable option from [`PressioCompressor::get_options`].
+    Tunable,
+}
+
+/// A single documented option of a compressor.
+#[derive(Clone, Debug)]
+pub struct PressioSchemaEntry {
+    /// The full option key, e.g. `sz:abs_err_bound`.
+    pub key: String,
+    /// The key without its namespace, e.g. `abs_err_bound`, which is its key
+    /// in the [`PressioOptions::subtree`] of the namespace.
+    pub name: String,
+    /// The top-level namespace of the key, e.g. `sz`, as listed by
+    /// [`PressioOptions::namespaces`], or [`None`] for an un-namespaced key.
+    pub namespace: Option<String>,
+    /// The type of the option, with [`None`] as its value.
+    pub option_type: PressioOption,
+    /// The value reported by the compressor when the schema was built, i.e.
+    /// including any options that were set on it before.
+    pub default: PressioOption,
+    /// The documentation string, if the compressor provides one.
+    pub doc: Option<String>,
+    pub kind: PressioOptionKind,
+}
+
+/// The options of a compressor, joined with their documentation.
+///
+/// The schema is built from [`PressioCompressor::get_options`],
+/// [`PressioCompressor::get_configuration`], and
+/// [`PressioCompressor::get_documentation`].
+#[derive(Clone, Debug)]
+pub struct CompressorSchema {
+    pub prefix: String,
+    pub description: Option<String>,
+    entries: BTreeMap<String, PressioSchemaEntry>,
+}
+
+impl CompressorSchema {
+    pub fn from_compressor(compressor: &PressioCompressor) -> Result<Self, PressioError> {
+        let prefix = String::from(compressor.get_prefix()?);
+        let options = compressor.get_options()?;
+        let configuration = compressor.get_configuration()?;
+        let documentation = compressor.get_documentation()?;
+
+        let mut entries = BTreeMap::new();
+
+        // tunable options are inserted last so that they take precedence
+        //  over configuration entries with the same key
+        for (kind, source) in [
+            (PressioOptionKind::Configuration, &configuration),
+            (PressioOptionKind::Tunable, &options),
+        ] {
+            for (key, option) in source.iter() {
+                let (Some(key), Some(option)) = (key, option) else {
+                    continue;
+                };
+
+                let doc = match documentation.get(&key)? {
+                    Some(PressioOption::string(doc)) => doc,
+                    _ => None,
+                };
+
+                let (namespace, name) = match key.split_once(':') {
+                    Some((namespace, name)) => (Some(String::from(namespace)), String::from(name)),
+                    None => (None, key.clone()),
+                };
+
+                entries.insert(
+                    key.clone(),
+                    PressioSchemaEntry {
+                        key,
+                        name,
+                        namespace,
+                        option_type: option.copy_type_only(),
+                        default: option,
+                        doc,
+                        kind,
+                    },
+                );
+            }
+        }
+
+        let description = [
+            format!("{prefix}:pressio:description"),
+            String::from("pressio:description"),
+        ]
+        .into_iter()
+        .find_map(|key| match documentation.get(key) {
+            Ok(Some(PressioOption::string(Some(description)))) => Some(description),
+            _ => None,
+        });
+
+        Ok(Self {
+            prefix,
+            description,
+            entries,
+        })
+    }
+
+    pub fn get(&self, key: impl AsRef<str>) -> Option<&PressioSchemaEntry> {
+        self.entries.get(key.as_ref())
+    }
+
+    /// Iterate over all entries, sorted by their key.
+    pub fn entries(&self) -> impl ExactSizeIterator<Item = &PressioSchemaEntry> + '_ {
+        self.entries.values()
+    }
+
+    pub fn tunable(&self) -> impl Iterator<Item = &PressioSchemaEntry> + '_ {
+        self.entries
+            .values()
+            .filter(|entry| entry.kind == PressioOptionKind::Tunable)
+    }
+
+    pub fn configuration(&self) -> impl Iterator<Item = &PressioSchemaEntry> + '_ {
+        self.entries
+            .values()
+            .filter(|entry| entry.kind == PressioOptionKind::Configuration)
+    }
+
+    /// Iterate over the distinct namespaces of all entries, in sorted order.
+    pub fn namespaces(&self) -> impl Iterator<Item = &str> + '_ {
+        self.entries
+            .values()
+            .filter_map(|entry| entry.namespace.as_deref())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+    }
+
+    /// Build [`PressioOptions`] that contain the default values of all
+    /// tunable entries.
+    pub fn defaults(&self) -> Result<PressioOptions, PressioError> {
+        let mut options = PressioOptions::new()?;
+        for entry in self.tunable() {
+            options.set(&entry.key, entry.default.clone())?;
+        }
+        Ok(options)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
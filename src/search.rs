@@ -0,0 +1,432 @@
+use std::collections::HashMap;
+
+use crate::{ErrorBound, PressioCompressor, PressioData, PressioDtype, PressioError};
+
+/// The error bound mode that a search varies.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SearchMode {
+    Absolute,
+    ValueRangeRelative,
+    PointwiseRelative,
+}
+
+impl SearchMode {
+    pub const fn bound(self, value: f64) -> ErrorBound {
+        match self {
+            Self::Absolute => ErrorBound::Absolute(value),
+            Self::ValueRangeRelative => ErrorBound::ValueRangeRelative(value),
+            Self::PointwiseRelative => ErrorBound::PointwiseRelative(value),
+        }
+    }
+
+    const fn default_interval(self) -> (f64, f64) {
+        match self {
+            Self::Absolute => (1e-12, 1e6),
+            Self::ValueRangeRelative | Self::PointwiseRelative => (1e-12, 1.0),
+        }
+    }
+}
+
+/// Search for the error bound at which a compressor reaches a target
+/// compression ratio.
+///
+/// The ratio is read from the `size:compression_ratio` metric, so the
+/// compressor must have the `size` metric enabled, e.g. with
+/// `pressio:metric=size`. The search assumes that the compression ratio grows
+/// with the error bound and combines secant steps with bisection in
+/// log-space.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RatioSearch {
+    pub mode: SearchMode,
+    pub target_ratio: f64,
+    /// The accepted relative deviation from the target ratio.
+    pub tolerance: f64,
+    /// The smallest and largest error bound that are tried.
+    pub interval: (f64, f64),
+    pub max_iterations: usize,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RatioSearchStep {
+    pub bound: ErrorBound,
+    pub ratio: f64,
+}
+
+#[derive(Debug)]
+pub struct RatioSearchResult {
+    /// The error bound whose ratio is closest to the target.
+    pub bound: ErrorBound,
+    pub ratio: f64,
+    /// The data compressed with [`RatioSearchResult::bound`].
+    pub compressed: PressioData,
+    /// Whether the ratio is within the tolerance of the target.
+    pub converged: bool,
+    /// All tried error bounds, in the order in which they were tried.
+    pub trace: Vec<RatioSearchStep>,
+}
+
+impl RatioSearch {
+    pub const fn new(mode: SearchMode, target_ratio: f64) -> Self {
+        Self {
+            mode,
+            target_ratio,
+            tolerance: 0.05,
+            interval: mode.default_interval(),
+            max_iterations: 32,
+        }
+    }
+
+    /// Run the search, after which the compressor is set to the returned
+    /// [`RatioSearchResult::bound`].
+    pub fn run(
+        &self,
+        compressor: &mut PressioCompressor,
+        input: &PressioData,
+    ) -> Result<RatioSearchResult, PressioError> {
+        let (lower, upper) = self.interval;
+        if !(lower > 0.0 && lower < upper && self.target_ratio > 0.0) {
+            return Err(PressioError {
+                error_code: 1,
+                message: format!(
+                    "invalid ratio search for a ratio of {} in the interval [{lower}, {upper}]",
+                    self.target_ratio
+                ),
+            });
+        }
+
+        let mut trace = Vec::new();
+        let mut best: Option<(RatioSearchStep, PressioData)> = None;
+
+        // evaluate the ratio at an error bound, returning the log-distance
+        //  from the target ratio
+        let mut evaluate = |bound: f64| -> Result<f64, PressioError> {
+            let bound = self.mode.bound(bound);
+            compressor.set_error_bound(bound)?;
+            let compressed =
+                compressor.compress(input, PressioData::new_empty(PressioDtype::Byte, []))?;
+            let ratio = compression_ratio(compressor)?;
+
+            let step = RatioSearchStep { bound, ratio };
+            trace.push(step);
+
+            let distance = (ratio / self.target_ratio).ln();
+            if best.as_ref().is_none_or(|(best, _)| {
+                distance.abs() < (best.ratio / self.target_ratio).ln().abs()
+            }) {
+                best = Some((step, compressed));
+            }
+
+            Ok(distance)
+        };
+
+        let (mut x_lo, mut x_hi) = (lower.ln(), upper.ln());
+        let mut y_lo = evaluate(lower)?;
+        let mut y_hi = evaluate(upper)?;
+
+        let tolerance = (1.0 + self.tolerance).ln();
+
+        // only search if the target is bracketed by the interval
+        if y_lo < -tolerance && y_hi > tolerance {
+            for _ in 2..self.max_iterations {
+                let x = secant_or_bisection(x_lo, y_lo, x_hi, y_hi);
+                let y = evaluate(x.exp())?;
+
+                if y.abs() <= tolerance {
+                    break;
+                }
+                if y < 0.0 {
+                    (x_lo, y_lo) = (x, y);
+                } else {
+                    (x_hi, y_hi) = (x, y);
+                }
+            }
+        }
+
+        let Some((step, compressed)) = best else {
+            unreachable!("the ratio search evaluates at least two error bounds");
+        };
+
+        compressor.set_error_bound(step.bound)?;
+
+        Ok(RatioSearchResult {
+            bound: step.bound,
+            ratio: step.ratio,
+            compressed,
+            converged: (step.ratio / self.target_ratio).ln().abs() <= tolerance,
+            trace,
+        })
+    }
+}
+
+fn compression_ratio(compressor: &PressioCompressor) -> Result<f64, PressioError> {
+    const KEY: &str = "size:compression_ratio";
+
+    match compressor
+        .get_metric_results()?
+        .get(KEY)?
+        .and_then(|ratio| ratio.as_f64())
+    {
+        Some(ratio) => Ok(ratio),
+        None => Err(PressioError {
+            error_code: 1,
+            message: format!(
+                "compressor `{}` did not report the `{KEY}` metric, enable it with `pressio:metric=size`",
+                compressor.get_prefix()?
+            ),
+        }),
+    }
+}
+
+/// Pick the next point inside the bracket `[x_lo, x_hi]` using a secant step
+/// through `(x_lo, y_lo)` and `(x_hi, y_hi)`, falling back to bisection if the
+/// secant step would land close to the edges of the bracket.
+fn secant_or_bisection(x_lo: f64, y_lo: f64, x_hi: f64, y_hi: f64) -> f64 {
+    let width = x_hi - x_lo;
+    let x = x_lo - y_lo * width / (y_hi - y_lo);
+
+    if x.is_finite() && x > x_lo + 0.1 * width && x < x_hi - 0.1 * width {
+        x
+    } else {
+        x_lo + 0.5 * width
+    }
+}
+
+/// A quality metric computed by a libpressio metrics plugin.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum QualityMetric {
+    /// Peak signal-to-noise ratio in dB from the `error_stat` metric, which
+    /// must be at least the threshold.
+    Psnr,
+    /// Maximum absolute error from the `error_stat` metric, which must be at
+    /// most the threshold.
+    MaxAbsError,
+    /// Structural similarity index from the `ssim` metric, which must be at
+    /// least the threshold.
+    Ssim,
+}
+
+impl QualityMetric {
+    /// The id of the libpressio metrics plugin that computes this metric.
+    pub const fn metric_id(self) -> &'static str {
+        match self {
+            Self::Psnr | Self::MaxAbsError => "error_stat",
+            Self::Ssim => "ssim",
+        }
+    }
+
+    /// The key of this metric in the
+    /// [`PressioCompressor::get_metric_results`].
+    pub const fn result_key(self) -> &'static str {
+        match self {
+            Self::Psnr => "error_stat:psnr",
+            Self::MaxAbsError => "error_stat:max_error",
+            Self::Ssim => "ssim:ssim",
+        }
+    }
+
+    /// Signed distance of the `value` from the `threshold`, which is
+    /// non-negative iff the value meets the threshold.
+    fn margin(self, value: f64, threshold: f64) -> f64 {
+        match self {
+            Self::Psnr | Self::Ssim => value - threshold,
+            Self::MaxAbsError => threshold.ln() - value.ln(),
+        }
+    }
+}
+
+/// Search for the largest error bound at which a compressor still meets a
+/// quality threshold, e.g. a PSNR of at least 80 dB.
+///
+/// Every trial compresses and decompresses the input and reads the quality
+/// metric from [`PressioCompressor::get_metric_results`], so the compressor
+/// must have the [`QualityMetric::metric_id`] metric enabled, e.g. with
+/// `pressio:metric=error_stat`. The search assumes that the quality degrades
+/// as the error bound grows and bisects in log-space.
+///
+/// Trial results are cached across [`QualitySearch::run`]s, e.g. to search
+/// for several thresholds. The cache assumes that the same compressor and
+/// input are used for every run, otherwise [`QualitySearch::clear_cache`]
+/// must be called first.
+#[derive(Clone, Debug)]
+pub struct QualitySearch {
+    pub mode: SearchMode,
+    pub metric: QualityMetric,
+    pub threshold: f64,
+    /// The relative precision with which the largest error bound is found.
+    pub tolerance: f64,
+    /// The smallest and largest error bound that are tried.
+    pub interval: (f64, f64),
+    pub max_iterations: usize,
+    cache: HashMap<(SearchMode, QualityMetric, u64), f64>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct QualitySearchStep {
+    pub bound: ErrorBound,
+    pub value: f64,
+    /// Whether the value was taken from the cache of an earlier trial.
+    pub cached: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct QualitySearchResult {
+    /// The largest tried error bound that meets the threshold, or [`None`]
+    /// if even the smallest error bound of the interval does not.
+    pub bound: Option<ErrorBound>,
+    /// The metric value at [`QualitySearchResult::bound`].
+    pub value: Option<f64>,
+    /// All tried error bounds, in the order in which they were tried.
+    pub trace: Vec<QualitySearchStep>,
+}
+
+impl QualitySearch {
+    pub fn new(mode: SearchMode, metric: QualityMetric, threshold: f64) -> Self {
+        Self {
+            mode,
+            metric,
+            threshold,
+            tolerance: 0.01,
+            interval: mode.default_interval(),
+            max_iterations: 32,
+            cache: HashMap::new(),
+        }
+    }
+
+    pub fn clear_cache(&mut self) {
+        self.cache.clear();
+    }
+
+    /// Run the search, after which the compressor is set to the returned
+    /// [`QualitySearchResult::bound`], or to the smallest error bound of the
+    /// interval if no error bound meets the threshold.
+    pub fn run(
+        &mut self,
+        compressor: &mut PressioCompressor,
+        input: &PressioData,
+    ) -> Result<QualitySearchResult, PressioError> {
+        let (lower, upper) = self.interval;
+        if !(lower > 0.0 && lower < upper && self.tolerance > 0.0) {
+            return Err(PressioError {
+                error_code: 1,
+                message: format!(
+                    "invalid quality search with a tolerance of {} in the interval [{lower}, {upper}]",
+                    self.tolerance
+                ),
+            });
+        }
+
+        let Some(dtype) = input.dtype() else {
+            return Err(PressioError {
+                error_code: 1,
+                message: String::from(
+                    "cannot search the error bound for data with an unknown dtype",
+                ),
+            });
+        };
+        let shape = input.shape();
+
+        let Self {
+            mode,
+            metric,
+            threshold,
+            ..
+        } = *self;
+
+        let mut trace = Vec::new();
+
+        let mut evaluate = |bound: f64| -> Result<(f64, f64), PressioError> {
+            let key = (mode, metric, bound.to_bits());
+            let bound = mode.bound(bound);
+
+            let (value, cached) = match self.cache.get(&key) {
+                Some(value) => (*value, true),
+                None => {
+                    compressor.set_error_bound(bound)?;
+                    let compressed = compressor
+                        .compress(input, PressioData::new_empty(PressioDtype::Byte, []))?;
+                    compressor.decompress(&compressed, PressioData::new_empty(dtype, &shape))?;
+                    let value = quality(compressor, metric)?;
+                    self.cache.insert(key, value);
+                    (value, false)
+                }
+            };
+
+            trace.push(QualitySearchStep {
+                bound,
+                value,
+                cached,
+            });
+
+            Ok((value, metric.margin(value, threshold)))
+        };
+
+        let (mut x_lo, mut x_hi) = (lower.ln(), upper.ln());
+
+        let (value_hi, y_hi) = evaluate(upper)?;
+        if y_hi >= 0.0 {
+            compressor.set_error_bound(mode.bound(upper))?;
+            return Ok(QualitySearchResult {
+                bound: Some(mode.bound(upper)),
+                value: Some(value_hi),
+                trace,
+            });
+        }
+
+        let (mut value_lo, y_lo) = evaluate(lower)?;
+        if y_lo < 0.0 {
+            compressor.set_error_bound(mode.bound(lower))?;
+            return Ok(QualitySearchResult {
+                bound: None,
+                value: None,
+                trace,
+            });
+        }
+
+        let (mut y_lo, mut y_hi) = (y_lo, y_hi);
+        let tolerance = self.tolerance.ln_1p();
+
+        for _ in 2..self.max_iterations {
+            if x_hi - x_lo <= tolerance {
+                break;
+            }
+
+            let x = secant_or_bisection(x_lo, y_lo, x_hi, y_hi);
+            let (value, y) = evaluate(x.exp())?;
+
+            if y >= 0.0 {
+                (x_lo, y_lo, value_lo) = (x, y, value);
+            } else {
+                (x_hi, y_hi) = (x, y);
+            }
+        }
+
+        let bound = mode.bound(x_lo.exp());
+        compressor.set_error_bound(bound)?;
+
+        Ok(QualitySearchResult {
+            bound: Some(bound),
+            value: Some(value_lo),
+            trace,
+        })
+    }
+}
+
+fn quality(compressor: &PressioCompressor, metric: QualityMetric) -> Result<f64, PressioError> {
+    let key = metric.result_key();
+
+    match compressor
+        .get_metric_results()?
+        .get(key)?
+        .and_then(|value| value.as_f64())
+    {
+        Some(value) => Ok(value),
+        None => Err(PressioError {
+            error_code: 1,
+            message: format!(
+                "compressor `{}` did not report the `{key}` metric, enable it with `pressio:metric={}`",
+                compressor.get_prefix()?,
+                metric.metric_id(),
+            ),
+        }),
+    }
+}
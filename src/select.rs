@@ -0,0 +1,242 @@
+use ndarray::{ArrayViewD, IxDyn, SliceInfoElem};
+
+use crate::{PressioData, PressioDtype, PressioError};
+
+impl PressioData {
+    /// Select a hyperslab of the data, with the same semantics as in HDF5.
+    ///
+    /// Along each dimension `d`, `count[d]` blocks of `block[d]` consecutive
+    /// elements are selected, where the `i`-th block starts at index
+    /// `start[d] + i * stride[d]`. The selected data has the shape
+    /// `count[d] * block[d]`.
+    ///
+    /// The dimensions are indexed in the same order as
+    /// [`PressioData::shape`], with the last dimension varying fastest in
+    /// memory. An error is returned if the data has no values, if the
+    /// arguments do not have one entry per dimension, if the blocks overlap,
+    /// or if the selection is out of bounds.
+    pub fn select(
+        &self,
+        start: &[usize],
+        stride: &[usize],
+        count: &[usize],
+        block: &[usize],
+    ) -> Result<Self, PressioError> {
+        let shape = self.shape();
+
+        if [start, stride, count, block]
+            .iter()
+            .any(|arg| arg.len() != shape.len())
+        {
+            return Err(select_error(&format!(
+                "hyperslab for data with {} dimensions requires {} starts, strides, counts, and blocks",
+                shape.len(),
+                shape.len(),
+            )));
+        }
+
+        let indices = (0..shape.len())
+            .map(|d| {
+                let (start, stride, count, block) = (start[d], stride[d], count[d], block[d]);
+                if count > 1 && block > stride {
+                    return Err(select_error(&format!(
+                        "hyperslab blocks of {block} elements overlap with stride {stride} in dimension {d}"
+                    )));
+                }
+                if count > 0 && block > 0 && start + (count - 1) * stride + block > shape[d] {
+                    return Err(select_error(&format!(
+                        "hyperslab is out of bounds for dimension {d} with length {}",
+                        shape[d]
+                    )));
+                }
+                Ok((0..count)
+                    .flat_map(|i| (0..block).map(move |j| start + i * stride + j))
+                    .collect())
+            })
+            .collect::<Result<Vec<Vec<usize>>, _>>()?;
+
+        let out_shape = indices.iter().map(Vec::len).collect::<Vec<_>>();
+        self.gather(&indices, &out_shape)
+    }
+
+    /// Slice the data like an ndarray, e.g. using the [`ndarray::s!`] macro.
+    ///
+    /// Negative indices count from the end of a dimension, and negative steps
+    /// reverse it. An error is returned if the data has no values, if the
+    /// slice does not have one entry per dimension of the data, or if it is
+    /// out of bounds.
+    pub fn slice(&self, info: impl AsRef<[SliceInfoElem]>) -> Result<Self, PressioError> {
+        let info = info.as_ref();
+        let shape = self.shape();
+
+        let num_axes = info
+            .iter()
+            .filter(|elem| !matches!(elem, SliceInfoElem::NewAxis))
+            .count();
+        if num_axes != shape.len() {
+            return Err(select_error(&format!(
+                "slice with {num_axes} axes cannot be applied to data with {} dimensions",
+                shape.len()
+            )));
+        }
+
+        let mut indices = Vec::with_capacity(shape.len());
+        let mut out_shape = Vec::with_capacity(info.len());
+        let mut axes = shape.iter().copied().enumerate();
+
+        for elem in info {
+            match *elem {
+                SliceInfoElem::Slice { start, end, step } => {
+                    let Some((d, len)) = axes.next() else {
+                        unreachable!("the number of axes has been checked")
+                    };
+                    let start = absolute_index(start, len, d)?;
+                    let end = absolute_index(end.unwrap_or(len as isize), len, d)?.max(start);
+                    let axis_indices: Vec<usize> = match step {
+                        0 => return Err(select_error("slice step must not be zero")),
+                        1.. => (start..end).step_by(step.unsigned_abs()).collect(),
+                        ..0 => (start..end).rev().step_by(step.unsigned_abs()).collect(),
+                    };
+                    out_shape.push(axis_indices.len());
+                    indices.push(axis_indices);
+                }
+                SliceInfoElem::Index(index) => {
+                    let Some((d, len)) = axes.next() else {
+                        unreachable!("the number of axes has been checked")
+                    };
+                    let index = absolute_index(index, len, d)?;
+                    if index >= len {
+                        return Err(select_error(&format!(
+                            "index {index} is out of bounds for dimension {d} with length {len}"
+                        )));
+                    }
+                    indices.push(vec![index]);
+                }
+                SliceInfoElem::NewAxis => out_shape.push(1),
+            }
+        }
+
+        self.gather(&indices, &out_shape)
+    }
+
+    /// Permute the dimensions of the data, such that the `i`-th dimension of
+    /// the transposed data is the `axes[i]`-th dimension of this data.
+    ///
+    /// The elements are reordered, such that the transposed data is again
+    /// stored with its last dimension varying fastest. An error is returned
+    /// if the data has no values or if the `axes` are not a permutation of
+    /// its dimensions.
+    pub fn transpose(&self, axes: &[usize]) -> Result<Self, PressioError> {
+        let shape = self.shape();
+
+        let mut seen = vec![false; shape.len()];
+        for &axis in axes {
+            match seen.get_mut(axis) {
+                Some(seen @ false) => *seen = true,
+                _ => {
+                    return Err(select_error(&format!(
+                        "axes {axes:?} are not a permutation of {} dimensions",
+                        shape.len()
+                    )));
+                }
+            }
+        }
+        if axes.len() != shape.len() {
+            return Err(select_error(&format!(
+                "axes {axes:?} are not a permutation of {} dimensions",
+                shape.len()
+            )));
+        }
+
+        let (dtype, bytes) = self.dtype_and_bytes()?;
+        let size = dtype.size_in_bytes();
+
+        // view the bytes with an extra trailing axis for the bytes of each
+        //  element, which stays in place
+        let mut byte_shape = shape.clone();
+        byte_shape.push(size);
+        let Ok(view) = ArrayViewD::from_shape(IxDyn(&byte_shape), bytes) else {
+            unreachable!("the data has as many bytes as its shape requires")
+        };
+        let mut byte_axes = axes.to_vec();
+        byte_axes.push(shape.len());
+        let transposed = view.permuted_axes(IxDyn(&byte_axes));
+
+        let out_shape = axes.iter().map(|&axis| shape[axis]).collect::<Vec<_>>();
+        let out_bytes = transposed.iter().copied().collect::<Vec<_>>();
+        Ok(Self::from_bytes(dtype, &out_shape, &out_bytes))
+    }
+
+    /// Copy the elements at the cartesian product of the per-dimension
+    /// `indices` into new data with the `out_shape`, which must have as many
+    /// elements as the product.
+    fn gather(&self, indices: &[Vec<usize>], out_shape: &[usize]) -> Result<Self, PressioError> {
+        let (dtype, bytes) = self.dtype_and_bytes()?;
+        let size = dtype.size_in_bytes();
+        let shape = self.shape();
+
+        let mut strides = vec![size; shape.len()];
+        for d in (1..shape.len()).rev() {
+            strides[d - 1] = strides[d] * shape[d];
+        }
+
+        let len = indices.iter().map(Vec::len).product::<usize>();
+        let mut out = Vec::with_capacity(len * size);
+
+        if len == 0 {
+            return Ok(Self::from_bytes(dtype, out_shape, &out));
+        }
+
+        let mut position = vec![0; indices.len()];
+        loop {
+            let offset = position
+                .iter()
+                .zip(indices)
+                .zip(&strides)
+                .map(|((&i, indices), stride)| indices[i] * stride)
+                .sum::<usize>();
+            out.extend_from_slice(&bytes[offset..offset + size]);
+
+            // advance the position like an odometer, last dimension first
+            let Some(d) = (0..indices.len())
+                .rev()
+                .find(|&d| position[d] + 1 < indices[d].len())
+            else {
+                break;
+            };
+            position[d] += 1;
+            position[d + 1..].fill(0);
+        }
+
+        Ok(Self::from_bytes(dtype, out_shape, &out))
+    }
+
+    fn dtype_and_bytes(&self) -> Result<(PressioDtype, &[u8]), PressioError> {
+        match (self.dtype(), self.as_bytes()) {
+            (Some(dtype), Some(bytes)) => Ok((dtype, bytes)),
+            _ => Err(select_error("cannot select from data without values")),
+        }
+    }
+}
+
+fn absolute_index(index: isize, len: usize, d: usize) -> Result<usize, PressioError> {
+    let absolute = if index < 0 {
+        len.checked_sub(index.unsigned_abs())
+    } else {
+        Some(index.unsigned_abs())
+    };
+
+    match absolute {
+        Some(absolute) if absolute <= len => Ok(absolute),
+        _ => Err(select_error(&format!(
+            "index {index} is out of bounds for dimension {d} with length {len}"
+        ))),
+    }
+}
+
+fn select_error(message: &str) -> PressioError {
+    PressioError {
+        error_code: 1,
+        message: String::from(message),
+    }
+}
@@ -0,0 +1,118 @@
+use ndarray::ArrayViewD;
+
+use crate::{NumericVisitor, PressioData, PressioError, PressioNumeric};
+
+/// Summary statistics of numeric [`PressioData`], see [`PressioData::stats`].
+///
+/// The minimum, maximum, range, mean, and variance only consider finite
+/// values, and are NaN if the data has no finite values.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DataStats {
+    pub num_elements: usize,
+    pub min: f64,
+    pub max: f64,
+    /// The value range `max - min`, which e.g. scales value-range relative
+    /// error bounds.
+    pub range: f64,
+    pub mean: f64,
+    /// The population variance.
+    pub variance: f64,
+    pub num_nan: usize,
+    pub num_inf: usize,
+    /// The fraction of elements that are zero.
+    pub zero_fraction: f64,
+}
+
+impl DataStats {
+    pub fn std_dev(&self) -> f64 {
+        self.variance.sqrt()
+    }
+
+    /// The number of finite elements.
+    pub fn num_finite(&self) -> usize {
+        self.num_elements - self.num_nan - self.num_inf
+    }
+}
+
+impl PressioData {
+    /// Compute summary statistics of the data, without copying it.
+    ///
+    /// An error is returned if the data has no values or if its dtype is not
+    /// numeric.
+    pub fn stats(&self) -> Result<DataStats, PressioError> {
+        self.map_numeric(StatsVisitor).ok_or_else(|| PressioError {
+            error_code: 1,
+            message: String::from("cannot compute statistics of data without numeric values"),
+        })
+    }
+}
+
+struct StatsVisitor;
+
+impl NumericVisitor for StatsVisitor {
+    type Output = DataStats;
+
+    fn visit<T: PressioNumeric>(self, array: ArrayViewD<'_, T>) -> Self::Output {
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        let mut num_finite = 0_usize;
+        let mut mean = 0.0;
+        let mut m2 = 0.0;
+        let mut num_nan = 0;
+        let mut num_inf = 0;
+        let mut num_zero = 0_usize;
+
+        for &x in &array {
+            let x = x.to_f64();
+
+            if x.is_nan() {
+                num_nan += 1;
+                continue;
+            }
+            if x.is_infinite() {
+                num_inf += 1;
+                continue;
+            }
+            if x == 0.0 {
+                num_zero += 1;
+            }
+
+            min = min.min(x);
+            max = max.max(x);
+
+            // Welford's online algorithm for a numerically stable variance
+            num_finite += 1;
+            let delta = x - mean;
+            mean += delta / num_finite as f64;
+            m2 += delta * (x - mean);
+        }
+
+        let num_elements = array.len();
+
+        if num_finite == 0 {
+            return DataStats {
+                num_elements,
+                min: f64::NAN,
+                max: f64::NAN,
+                range: f64::NAN,
+                mean: f64::NAN,
+                variance: f64::NAN,
+                num_nan,
+                num_inf,
+                zero_fraction: 0.0,
+            };
+        }
+
+        DataStats {
+            num_elements,
+            min,
+            max,
+            range: max - min,
+            mean,
+            variance: m2 / num_finite as f64,
+            num_nan,
+            num_inf,
+            zero_fraction: num_zero as f64 / num_elements as f64,
+        }
+    }
+}
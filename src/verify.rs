@@ -0,0 +1,264 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+};
+
+use ndarray::ArrayViewD;
+
+use crate::{
+    ErrorBound, NumericVisitor, PressioArray, PressioCompressor, PressioData, PressioDtype,
+    PressioError, PressioNumeric,
+};
+
+/// The number of worst offending elements that are kept in a
+/// [`RoundtripReport`].
+const MAX_REPORTED_VIOLATIONS: usize = 16;
+
+/// An element whose reconstruction violates the error bound.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundtripViolation {
+    /// The multi-dimensional index of the element, in the order of
+    /// [`PressioData::shape`].
+    pub index: Vec<usize>,
+    pub expected: f64,
+    pub actual: f64,
+    /// The absolute error of the reconstruction.
+    pub error: f64,
+    /// The largest absolute error that the bound allows for this element.
+    pub allowed: f64,
+}
+
+#[derive(Debug)]
+pub struct RoundtripReport {
+    pub bound: ErrorBound,
+    pub num_elements: usize,
+    pub compressed_bytes: usize,
+    pub max_abs_error: f64,
+    pub num_violations: usize,
+    /// The worst violations, sorted by how much they exceed the bound.
+    ///
+    /// For [`ErrorBound::Lossless`], the first violations in index order are
+    /// reported instead.
+    pub worst: Vec<RoundtripViolation>,
+    pub decompressed: PressioData,
+}
+
+impl RoundtripReport {
+    pub fn is_ok(&self) -> bool {
+        self.num_violations == 0
+    }
+}
+
+/// Compress and decompress the `input` and check element-wise that the
+/// reconstruction respects the error `bound`.
+///
+/// The compressor must already be configured for the bound, e.g. with
+/// [`PressioCompressor::set_error_bound`]. The input is decompressed into a
+/// buffer with the same dtype and shape as the input. For
+/// [`ErrorBound::Lossless`], the reconstruction must be bit-exact.
+///
+/// An error is returned if compression or decompression fails, if the
+/// decompressed data does not have the dtype and shape of the input, or if
+/// the bound cannot be checked element-wise, i.e. for
+/// [`ErrorBound::FixedRate`] and [`ErrorBound::FixedPrecision`].
+pub fn verify_roundtrip(
+    compressor: &mut PressioCompressor,
+    input: &PressioData,
+    bound: ErrorBound,
+) -> Result<RoundtripReport, PressioError> {
+    if let ErrorBound::FixedRate(_) | ErrorBound::FixedPrecision(_) = bound {
+        return Err(PressioError {
+            error_code: 1,
+            message: format!("the error bound `{bound}` cannot be verified element-wise"),
+        });
+    }
+
+    let (Some(dtype), Some(expected)) = (input.dtype(), input.clone_into_array()) else {
+        return Err(PressioError {
+            error_code: 1,
+            message: String::from("cannot verify the roundtrip of data without a dtype or values"),
+        });
+    };
+    let shape = input.shape();
+
+    let compressed = compressor.compress(input, PressioData::new_empty(PressioDtype::Byte, []))?;
+    let decompressed = compressor.decompress(&compressed, PressioData::new_empty(dtype, &shape))?;
+
+    let actual = match decompressed.clone_into_array() {
+        Some(actual) if actual.dtype() == dtype && decompressed.shape() == shape => actual,
+        _ => {
+            return Err(PressioError {
+                error_code: 1,
+                message: format!(
+                    "decompressed data has dtype {:?} and shape {:?} but expected {dtype} data with shape {shape:?}",
+                    decompressed.dtype(),
+                    decompressed.shape()
+                ),
+            });
+        }
+    };
+
+    let expected_values = to_f64_values(&expected);
+    let actual_values = to_f64_values(&actual);
+
+    let value_range = expected_values
+        .iter()
+        .copied()
+        .filter(|x| x.is_finite())
+        .fold(None, |range: Option<(f64, f64)>, x| match range {
+            None => Some((x, x)),
+            Some((min, max)) => Some((min.min(x), max.max(x))),
+        })
+        .map_or(0.0, |(min, max)| max - min);
+
+    let exact = if let ErrorBound::Lossless = bound {
+        let (Some(expected_bytes), Some(actual_bytes)) =
+            (input.as_bytes(), decompressed.as_bytes())
+        else {
+            unreachable!("both the input and the decompressed data have values");
+        };
+        Some((expected_bytes, actual_bytes, dtype.size_in_bytes()))
+    } else {
+        None
+    };
+
+    let mut max_abs_error = 0.0_f64;
+    let mut num_violations = 0;
+    let mut violations = BinaryHeap::with_capacity(MAX_REPORTED_VIOLATIONS);
+
+    for (i, (&expected, &actual)) in expected_values.iter().zip(&actual_values).enumerate() {
+        let error = (expected - actual).abs();
+        if !error.is_nan() {
+            max_abs_error = max_abs_error.max(error);
+        }
+
+        let allowed = match bound {
+            ErrorBound::Absolute(bound) => bound,
+            ErrorBound::ValueRangeRelative(bound) => bound * value_range,
+            ErrorBound::PointwiseRelative(bound) => bound * expected.abs(),
+            ErrorBound::Lossless | ErrorBound::FixedRate(_) | ErrorBound::FixedPrecision(_) => 0.0,
+        };
+
+        let violated = match &exact {
+            Some((expected_bytes, actual_bytes, size)) => {
+                expected_bytes[i * size..(i + 1) * size] != actual_bytes[i * size..(i + 1) * size]
+            }
+            None if expected.is_nan() => !actual.is_nan(),
+            // infinities must be reconstructed exactly
+            None if expected.is_infinite() => expected != actual,
+            None => error.is_nan() || error > allowed,
+        };
+
+        if !violated {
+            continue;
+        }
+        num_violations += 1;
+
+        // lossless violations are reported in order, so later ones never rank higher
+        if exact.is_some() && violations.len() == MAX_REPORTED_VIOLATIONS {
+            continue;
+        }
+        let violation = RankedViolation {
+            excess: match exact {
+                Some(_) => 0.0,
+                None if error.is_nan() => f64::INFINITY,
+                None => error - allowed,
+            },
+            index: i,
+            expected,
+            actual,
+            error,
+            allowed,
+        };
+        if violations.len() < MAX_REPORTED_VIOLATIONS {
+            violations.push(Reverse(violation));
+        } else if let Some(mut least) = violations.peek_mut() {
+            if violation > least.0 {
+                *least = Reverse(violation);
+            }
+        }
+    }
+
+    let worst = violations
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(violation)| RoundtripViolation {
+            index: unravel_index(violation.index, &shape),
+            expected: violation.expected,
+            actual: violation.actual,
+            error: violation.error,
+            allowed: violation.allowed,
+        })
+        .collect();
+
+    Ok(RoundtripReport {
+        bound,
+        num_elements: expected_values.len(),
+        compressed_bytes: compressed.num_bytes(),
+        max_abs_error,
+        num_violations,
+        worst,
+        decompressed,
+    })
+}
+
+/// A violating element that is ranked by how far its error exceeds the
+/// allowed error, with earlier elements ranking higher on ties.
+struct RankedViolation {
+    excess: f64,
+    index: usize,
+    expected: f64,
+    actual: f64,
+    error: f64,
+    allowed: f64,
+}
+
+impl Ord for RankedViolation {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.excess
+            .total_cmp(&other.excess)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for RankedViolation {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for RankedViolation {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for RankedViolation {}
+
+fn to_f64_values(array: &PressioArray) -> Vec<f64> {
+    struct ToF64Values;
+
+    impl NumericVisitor for ToF64Values {
+        type Output = Vec<f64>;
+
+        fn visit<T: PressioNumeric>(self, array: ArrayViewD<'_, T>) -> Self::Output {
+            array.iter().map(|&x| x.to_f64()).collect()
+        }
+    }
+
+    match array {
+        PressioArray::Bool(a) => a.iter().map(|&x| f64::from(u8::from(x))).collect(),
+        array => array.map_numeric(ToF64Values).unwrap_or_default(),
+    }
+}
+
+fn unravel_index(mut index: usize, shape: &[usize]) -> Vec<usize> {
+    let mut unraveled = vec![0; shape.len()];
+    for (i, &len) in shape.iter().enumerate().rev() {
+        if len > 0 {
+            unraveled[i] = index % len;
+            index /= len;
+        }
+    }
+    unraveled
+}
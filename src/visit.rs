@@ -0,0 +1,136 @@
+use ndarray::{Array, ArrayViewD, CowArray, IxDyn};
+
+use crate::{PressioArray, PressioData, PressioDtype, PressioElement};
+
+/// An element type of a numeric [`PressioDtype`](crate::PressioDtype), i.e.
+/// every element type except [`bool`].
+pub trait PressioNumeric: PressioElement + PartialOrd + std::fmt::Debug + Default {
+    /// Convert the value to [`f64`], rounding 64-bit integers to the nearest
+    /// representable value.
+    fn to_f64(self) -> f64;
+
+    /// Convert an [`f64`] to the nearest value, saturating at the bounds of
+    /// the type like an `as` cast.
+    fn from_f64(x: f64) -> Self;
+}
+
+macro_rules! impl_pressio_numeric {
+    ($($ty:ty => |$x:ident| $to_f64:expr),*) => {
+        $(
+            impl PressioNumeric for $ty {
+                fn to_f64(self) -> f64 {
+                    let $x = self;
+                    $to_f64
+                }
+
+                fn from_f64(x: f64) -> Self {
+                    x as $ty
+                }
+            }
+        )*
+    };
+}
+
+impl_pressio_numeric! {
+    u8 => |x| f64::from(x),
+    u16 => |x| f64::from(x),
+    u32 => |x| f64::from(x),
+    u64 => |x| x as f64,
+    i8 => |x| f64::from(x),
+    i16 => |x| f64::from(x),
+    i32 => |x| f64::from(x),
+    i64 => |x| x as f64,
+    f32 => |x| f64::from(x),
+    f64 => |x| x
+}
+
+/// A computation over an array of any numeric element type, see
+/// [`PressioArray::map_numeric`].
+///
+/// Since closures cannot be generic over the element type, the computation
+/// is implemented as a generic method on a visitor type instead.
+pub trait NumericVisitor {
+    type Output;
+
+    fn visit<T: PressioNumeric>(self, array: ArrayViewD<'_, T>) -> Self::Output;
+}
+
+impl PressioArray {
+    /// Apply the `visitor` to a view of the array with its concrete element
+    /// type.
+    ///
+    /// Returns [`None`] for [`PressioArray::Bool`] arrays, which are not
+    /// numeric.
+    pub fn map_numeric<V: NumericVisitor>(&self, visitor: V) -> Option<V::Output> {
+        match self {
+            Self::Bool(_) => None,
+            Self::Byte(a) | Self::U8(a) => Some(visitor.visit(a.view())),
+            Self::U16(a) => Some(visitor.visit(a.view())),
+            Self::U32(a) => Some(visitor.visit(a.view())),
+            Self::U64(a) => Some(visitor.visit(a.view())),
+            Self::I8(a) => Some(visitor.visit(a.view())),
+            Self::I16(a) => Some(visitor.visit(a.view())),
+            Self::I32(a) => Some(visitor.visit(a.view())),
+            Self::I64(a) => Some(visitor.visit(a.view())),
+            Self::F32(a) => Some(visitor.visit(a.view())),
+            Self::F64(a) => Some(visitor.visit(a.view())),
+        }
+    }
+
+    /// The array with the element type `T`, or [`None`] if the array has a
+    /// different element type.
+    ///
+    /// Both [`PressioArray::Byte`] and [`PressioArray::U8`] arrays have the
+    /// element type [`u8`].
+    pub fn as_array<T: PressioElement>(&self) -> Option<&Array<T, IxDyn>> {
+        T::as_array(self)
+    }
+
+    /// Convert into the array with the element type `T`, or return the
+    /// unchanged array if it has a different element type.
+    ///
+    /// Both [`PressioArray::Byte`] and [`PressioArray::U8`] arrays have the
+    /// element type [`u8`].
+    pub fn into_array<T: PressioElement>(self) -> Result<Array<T, IxDyn>, Self> {
+        T::into_array(self)
+    }
+}
+
+impl PressioData {
+    /// Apply the `visitor` to a view of the data with its concrete element
+    /// type, see [`PressioArray::map_numeric`].
+    ///
+    /// The data is only copied if it is not sufficiently aligned. Returns
+    /// [`None`] if the data has no values or if its dtype is
+    /// [`PressioDtype::Bool`].
+    pub fn map_numeric<V: NumericVisitor>(&self, visitor: V) -> Option<V::Output> {
+        fn visit_shared<T: PressioNumeric, V: NumericVisitor>(
+            data: &PressioData,
+            shape: &[usize],
+            visitor: V,
+        ) -> Option<V::Output> {
+            data.with_shared(IxDyn(shape), |array: CowArray<T, IxDyn>| {
+                visitor.visit(array.view())
+            })
+        }
+
+        let shape = self.shape();
+
+        match self.dtype()? {
+            PressioDtype::Bool => None,
+            PressioDtype::Byte => {
+                self.with_shared_bytes(IxDyn(&shape), |array| visitor.visit(array.view()))
+            }
+            PressioDtype::U8 => visit_shared::<u8, V>(self, &shape, visitor),
+            PressioDtype::U16 => visit_shared::<u16, V>(self, &shape, visitor),
+            PressioDtype::U32 => visit_shared::<u32, V>(self, &shape, visitor),
+            PressioDtype::U64 => visit_shared::<u64, V>(self, &shape, visitor),
+            PressioDtype::I8 => visit_shared::<i8, V>(self, &shape, visitor),
+            PressioDtype::I16 => visit_shared::<i16, V>(self, &shape, visitor),
+            PressioDtype::I32 => visit_shared::<i32, V>(self, &shape, visitor),
+            PressioDtype::I64 => visit_shared::<i64, V>(self, &shape, visitor),
+            PressioDtype::F32 => visit_shared::<f32, V>(self, &shape, visitor),
+            PressioDtype::F64 => visit_shared::<f64, V>(self, &shape, visitor),
+        }
+    }
+}